@@ -1,7 +1,9 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::systems::simulation::SIMULATION_HZ;
 
 /// Breath (stocks/lives) component - tracks remaining rounds a player can lose
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Breath {
     /// Current breaths remaining (0-3)
     pub current: u8,
@@ -24,6 +26,11 @@ impl Breath {
         }
     }
 
+    /// Breaths lost so far this match
+    pub fn lost(&self) -> u8 {
+        self.max.saturating_sub(self.current)
+    }
+
     /// Has this player lost all breaths?
     pub fn is_defeated(&self) -> bool {
         self.current == 0
@@ -41,33 +48,77 @@ impl Default for Breath {
     }
 }
 
+/// Desperation - down 0-2 in Breaths, fighting a cornered animal's fight
+///
+/// Applied and cleared in `breath::handle_round_end`, together with an
+/// immediate `FinalStand`; lasts until the player takes a Breath back or
+/// loses the match.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct Desperation;
+
+impl Desperation {
+    /// Damage multiplier on all of a desperate player's attacks
+    pub const DAMAGE_MULTIPLIER: f32 = 1.15;
+
+    /// Whether a player with `own` Breaths is desperate against `opponent`
+    pub fn is_earned(own: &Breath, opponent: &Breath) -> bool {
+        own.lost() >= 2 && opponent.lost() == 0 && !own.is_defeated()
+    }
+}
+
+/// Length of a round in simulation frames (60 seconds at 60 Hz)
+///
+/// Matches use `GameConfig::round_frames`; this is the fallback for a bare
+/// `MatchState::default()`.
+pub const ROUND_FRAMES: u32 = 60 * 60;
+
+/// Countdown before each round in simulation frames (3 seconds at 60 Hz)
+///
+/// Matches use `GameConfig::countdown_frames`.
+pub const COUNTDOWN_FRAMES: u32 = 3 * 60;
+
+fn default_countdown_length() -> u32 {
+    COUNTDOWN_FRAMES
+}
+
 /// Match state resource - tracks round/match state
-#[derive(Resource, Debug)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct MatchState {
     /// Current round number (1-based)
     pub round_number: u32,
-    /// Time remaining in current round (seconds)
-    pub round_time: f32,
-    /// Maximum round time (60 seconds)
-    pub max_round_time: f32,
+    /// Frames remaining in current round
+    pub round_frames: u32,
+    /// Maximum round length in frames
+    pub max_round_frames: u32,
     /// Is the round currently active?
     pub round_active: bool,
-    /// Countdown before round starts (3, 2, 1, 0 = fight!)
-    pub countdown: f32,
+    /// Frames left in the countdown before the round starts (0 = fight!)
+    pub countdown_frames: u32,
+    /// Countdown length before each round
+    #[serde(default = "default_countdown_length")]
+    pub countdown_length: u32,
     /// Is match over?
     pub match_over: bool,
     /// Winner entity (if match is over)
+    /// Not serialized: entity ids don't survive a restore (see `SimState`)
+    #[serde(skip)]
     pub winner: Option<Entity>,
 }
 
 impl MatchState {
     pub fn new() -> Self {
+        Self::with_timers(ROUND_FRAMES, COUNTDOWN_FRAMES)
+    }
+
+    /// New match with the given round length and pre-round countdown
+    pub fn with_timers(round_frames: u32, countdown_frames: u32) -> Self {
         Self {
             round_number: 1,
-            round_time: 60.0,
-            max_round_time: 60.0,
+            round_frames,
+            max_round_frames: round_frames,
             round_active: false,
-            countdown: 3.0,
+            countdown_frames,
+            countdown_length: countdown_frames,
             match_over: false,
             winner: None,
         }
@@ -76,15 +127,15 @@ impl MatchState {
     /// Start a new round
     pub fn start_round(&mut self) {
         self.round_active = true;
-        self.round_time = self.max_round_time;
-        self.countdown = 0.0;
+        self.round_frames = self.max_round_frames;
+        self.countdown_frames = 0;
     }
 
     /// End the current round
     pub fn end_round(&mut self) {
         self.round_active = false;
         self.round_number += 1;
-        self.countdown = 3.0;
+        self.countdown_frames = self.countdown_length;
     }
 
     /// End the match with a winner
@@ -96,15 +147,25 @@ impl MatchState {
 
     /// Is round time expired?
     pub fn is_timeout(&self) -> bool {
-        self.round_time <= 0.0
+        self.round_frames == 0
+    }
+
+    /// Round time remaining in seconds (for display)
+    pub fn round_seconds(&self) -> f32 {
+        self.round_frames as f32 / SIMULATION_HZ as f32
+    }
+
+    /// Countdown remaining in seconds (for display)
+    pub fn countdown_seconds(&self) -> f32 {
+        self.countdown_frames as f32 / SIMULATION_HZ as f32
     }
 
     /// Reset for new match
     pub fn reset(&mut self) {
         self.round_number = 1;
-        self.round_time = self.max_round_time;
+        self.round_frames = self.max_round_frames;
         self.round_active = false;
-        self.countdown = 3.0;
+        self.countdown_frames = self.countdown_length;
         self.match_over = false;
         self.winner = None;
     }
@@ -119,7 +180,7 @@ impl Default for MatchState {
 /// Round end reason for visual feedback
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RoundEndReason {
-    /// Decisive blow landed (Heavy/Grab on Broken opponent)
+    /// Decisive Blow landed on a Broken, staggered opponent
     DecisiveBlow,
     /// Round timer expired
     Timeout,
@@ -133,3 +194,22 @@ pub struct RoundEndEvent {
     /// Reason the round ended
     pub reason: RoundEndReason,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desperation_only_when_down_zero_two() {
+        let fresh = Breath::new(3);
+        let mut one_down = Breath::new(3);
+        one_down.lose_breath();
+        let mut two_down = one_down.clone();
+        two_down.lose_breath();
+
+        assert!(Desperation::is_earned(&two_down, &fresh));
+        assert!(!Desperation::is_earned(&two_down, &one_down));
+        assert!(!Desperation::is_earned(&one_down, &fresh));
+        assert!(!Desperation::is_earned(&fresh, &two_down));
+    }
+}
@@ -1,20 +1,116 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Marker component for characters
 #[derive(Component)]
 pub struct Character;
 
 /// Player identifier (1 or 2)
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Player {
     One,
     Two,
 }
 
 /// Maximum movement speed
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct MaxSpeed(pub f32);
 
 /// Current velocity
-#[derive(Component, Default)]
+#[derive(Component, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Velocity(pub Vec2);
+
+/// Which way a fighter faces
+///
+/// Updated when the fighters cross (see `movement::update_facing`); forward
+/// and back inputs, hitbox offsets, attack movement and stumble directions
+/// are all relative to it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Facing {
+    Right,
+    Left,
+}
+
+impl Facing {
+    /// Facing from `x` towards `target_x`
+    pub fn towards(x: f32, target_x: f32) -> Self {
+        if target_x < x {
+            Facing::Left
+        } else {
+            Facing::Right
+        }
+    }
+
+    /// 1.0 facing right, -1.0 facing left
+    pub fn sign(self) -> f32 {
+        match self {
+            Facing::Right => 1.0,
+            Facing::Left => -1.0,
+        }
+    }
+}
+
+/// Whether a fighter has its back to a stage wall (the Zugzwang zone)
+///
+/// Derived from the position each tick by `corner::update_corners`: within
+/// `corner_depth` of the stage limit counts as cornered. Wall bounces, dash,
+/// evade and hit pushback read it, and the HUD, controllers and headless
+/// results report it.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Corner {
+    #[default]
+    Midscreen,
+    /// Back to the left wall
+    Left,
+    /// Back to the right wall
+    Right,
+}
+
+impl Corner {
+    /// Corner state at `x` on a stage whose fighters stop at `±limit`
+    pub fn at(x: f32, limit: f32, depth: f32) -> Self {
+        if x <= -limit + depth {
+            Corner::Left
+        } else if x >= limit - depth {
+            Corner::Right
+        } else {
+            Corner::Midscreen
+        }
+    }
+
+    pub fn is_cornered(self) -> bool {
+        self != Corner::Midscreen
+    }
+
+    /// -1.0 against the left wall, 1.0 against the right, 0.0 midscreen
+    pub fn wall_sign(self) -> f32 {
+        match self {
+            Corner::Midscreen => 0.0,
+            Corner::Left => -1.0,
+            Corner::Right => 1.0,
+        }
+    }
+
+    /// Whether moving along `direction_x` heads into the wall behind
+    pub fn into_wall(self, direction_x: f32) -> bool {
+        direction_x * self.wall_sign() > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corner_from_position() {
+        assert_eq!(Corner::at(0.0, 470.0, 40.0), Corner::Midscreen);
+        assert_eq!(Corner::at(-440.0, 470.0, 40.0), Corner::Left);
+        assert_eq!(Corner::at(470.0, 470.0, 40.0), Corner::Right);
+        assert_eq!(Corner::at(420.0, 470.0, 40.0), Corner::Midscreen);
+
+        // Only the way into the wall is closed
+        assert!(Corner::Left.into_wall(-1.0));
+        assert!(!Corner::Left.into_wall(1.0));
+        assert!(!Corner::Midscreen.into_wall(-1.0));
+    }
+}
@@ -1,8 +1,9 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::components::stumble::StumbleDirection;
 
 /// Properties that modify attack behavior
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AttackProperty {
     /// Attack absorbs one light hit during startup (Heavy attacks)
     LightArmor,
@@ -13,7 +14,7 @@ pub enum AttackProperty {
 }
 
 /// Stumble/juggling properties for moves
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StumbleProperty {
     /// No stumble effect
     None,
@@ -32,25 +33,26 @@ impl Default for StumbleProperty {
 }
 
 /// Offensive hitbox - damages opponents when active
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Hitbox {
-    /// Rectangle offset from entity position (local space)
-    pub rect: Rect,
+    /// Rectangles offset from entity position (local space, already
+    /// mirrored for facing); set every active frame from the move's data
+    pub rects: Vec<Rect>,
     /// Whether this hitbox is currently active
     pub active: bool,
-    /// Amount of damage (in health states)
-    pub damage: u8,
     /// Special properties of this attack
     pub properties: Vec<AttackProperty>,
+    /// Whether this attack already hit; a move connects once
+    pub connected: bool,
 }
 
 impl Hitbox {
-    pub fn new(rect: Rect, damage: u8) -> Self {
+    pub fn new(rects: Vec<Rect>) -> Self {
         Self {
-            rect,
+            rects,
             active: false,
-            damage,
             properties: vec![],
+            connected: false,
         }
     }
 
@@ -67,23 +69,18 @@ impl Hitbox {
         self.active = false;
     }
 
-    /// Get world-space rect from entity transform
-    pub fn world_rect(&self, transform: &Transform) -> Rect {
-        Rect {
-            min: Vec2::new(
-                transform.translation.x + self.rect.min.x,
-                transform.translation.y + self.rect.min.y,
-            ),
-            max: Vec2::new(
-                transform.translation.x + self.rect.max.x,
-                transform.translation.y + self.rect.max.y,
-            ),
-        }
+    /// Get world-space rects from entity transform
+    pub fn world_rects(&self, transform: &Transform) -> Vec<Rect> {
+        let position = transform.translation.truncate();
+        self.rects
+            .iter()
+            .map(|rect| Rect::from_corners(rect.min + position, rect.max + position))
+            .collect()
     }
 }
 
 /// Defensive hurtbox - receives damage when hit
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Hurtbox {
     /// Rectangle offset from entity position (local space)
     pub rect: Rect,
@@ -115,3 +112,36 @@ impl Default for Hurtbox {
         Self::new(Rect::from_center_size(Vec2::ZERO, Vec2::new(100.0, 200.0)))
     }
 }
+
+/// Body collision box - fighters can't overlap these
+///
+/// Only the width matters while both fighters stand on the ground; see
+/// `movement::resolve_pushboxes`.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct Pushbox {
+    /// Body width while standing, walking, attacking or blocking
+    pub width: f32,
+    /// Narrower body while stumbling, so juggles carry closer to the attacker
+    pub stumble_width: f32,
+}
+
+impl Pushbox {
+    /// Half the width in use, given whether the fighter is stumbling
+    pub fn half_width(&self, stumbling: bool) -> f32 {
+        if stumbling {
+            self.stumble_width / 2.0
+        } else {
+            self.width / 2.0
+        }
+    }
+}
+
+impl Default for Pushbox {
+    fn default() -> Self {
+        // Narrower than the hurtbox so fighters can stand close enough to touch
+        Self {
+            width: 60.0,
+            stumble_width: 40.0,
+        }
+    }
+}
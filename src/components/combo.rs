@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::components::state::AttackType;
 
 /// Buffers recent button presses for lenient combo execution
@@ -6,7 +7,7 @@ use crate::components::state::AttackType;
 /// This component provides an 8-frame buffer window for inputs,
 /// making combo execution feel responsive and forgiving rather
 /// than requiring frame-perfect timing.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct InputBuffer {
     /// Frames since Light attack was pressed (0 = not buffered)
     /// Values 1-8 indicate the input is buffered
@@ -1,7 +1,8 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Guard meter - fills when blocking attacks, breaks when full
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct GuardMeter {
     /// Current guard meter value (0.0 to 1.0)
     pub current: f32,
@@ -43,3 +44,77 @@ impl Default for GuardMeter {
         Self::new()
     }
 }
+
+/// Left open by a Perfect parry: every hit taken counts as a counter hit
+/// until the window runs out
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct PunishWindow {
+    pub frames_remaining: u32,
+}
+
+/// How well a parry was timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParryGrade {
+    /// In the first frames of the window (or under Final Stand): full reward
+    Perfect,
+    /// Later in the window: the hit is negated, nothing more
+    Late,
+}
+
+/// Initiative a Final Stand parry steals, whatever the character's own
+/// `initiative_steal`
+pub const FINAL_STAND_INITIATIVE_STEAL: i32 = 20;
+
+/// A character's parry timing and rewards, from its character file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ParryData {
+    /// Parry window in frames
+    pub window_frames: u32,
+    /// Frames at the start of the window that grade Perfect
+    pub perfect_frames: u32,
+    /// Attacker stagger and counter-hit window after a Perfect parry
+    pub punish_frames: u32,
+    /// Initiative a Perfect parry steals from the attacker
+    pub initiative_steal: i32,
+    /// Guard meter a Perfect parry restores (1.0 = fully)
+    pub guard_restore: f32,
+}
+
+impl ParryData {
+    /// Grade a parry that caught a hit `elapsed` frames into its window
+    pub fn grade(&self, elapsed: u32) -> ParryGrade {
+        if elapsed < self.perfect_frames {
+            ParryGrade::Perfect
+        } else {
+            ParryGrade::Late
+        }
+    }
+}
+
+impl Default for ParryData {
+    fn default() -> Self {
+        Self {
+            window_frames: 6,
+            perfect_frames: 2,
+            punish_frames: 30,
+            initiative_steal: 20,
+            guard_restore: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parry_grade_by_timing() {
+        let parry = ParryData::default();
+
+        assert_eq!(parry.grade(0), ParryGrade::Perfect);
+        assert_eq!(parry.grade(1), ParryGrade::Perfect);
+        assert_eq!(parry.grade(2), ParryGrade::Late);
+        assert_eq!(parry.grade(5), ParryGrade::Late);
+    }
+}
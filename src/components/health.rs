@@ -1,7 +1,8 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Character health states that modify gameplay
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HealthState {
     /// 100-75% health - Full power, no restrictions
     Whole,
@@ -94,7 +95,7 @@ impl HealthState {
 }
 
 /// Character health component
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Health {
     /// Current health points (0.0 - 100.0)
     pub current: f32,
@@ -113,11 +114,9 @@ impl Health {
         }
     }
 
-    /// Update health state based on current health percentage
-    pub fn update_state(&mut self) {
-        let percentage = self.current / self.max;
-
-        self.state = if percentage > 0.75 {
+    /// Health state at a given health percentage
+    fn state_at(percentage: f32) -> HealthState {
+        if percentage > 0.75 {
             HealthState::Whole
         } else if percentage > 0.50 {
             HealthState::Cut
@@ -125,7 +124,12 @@ impl Health {
             HealthState::Wounded
         } else {
             HealthState::Broken
-        };
+        }
+    }
+
+    /// Update health state based on current health percentage
+    pub fn update_state(&mut self) {
+        self.state = Self::state_at(self.current / self.max);
     }
 
     /// Apply damage to health
@@ -134,6 +138,18 @@ impl Health {
         self.update_state();
     }
 
+    /// Apply chip damage from a blocked hit
+    ///
+    /// Chip forgiveness: blocking never leaves a fighter Broken, so chip that
+    /// would is dropped. A Broken fighter gets no forgiveness.
+    pub fn take_chip_damage(&mut self, damage: f32) {
+        let after = (self.current - damage).max(0.0);
+        if !self.is_broken() && Self::state_at(after / self.max) == HealthState::Broken {
+            return;
+        }
+        self.take_damage(damage);
+    }
+
     /// Restore health (for round resets)
     pub fn restore_full(&mut self) {
         self.current = self.max;
@@ -161,3 +177,31 @@ impl Default for Health {
         Self::new(100.0)
     }
 }
+
+/// Final Stand - the next successful parry is automatically Perfect
+///
+/// One-shot: granted on entering Broken (and on entering Desperation),
+/// consumed by `guard::check_parry_success`, dropped at round reset.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct FinalStand;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chip_forgiven_until_broken() {
+        let mut health = Health::new(100.0);
+        health.take_damage(70.0);
+
+        // Blocking can't take a Wounded fighter into Broken
+        health.take_chip_damage(10.0);
+        assert_eq!(health.current, 30.0);
+        assert_eq!(health.state, HealthState::Wounded);
+
+        // Once Broken, chip lands in full
+        health.take_damage(10.0);
+        health.take_chip_damage(10.0);
+        assert_eq!(health.current, 10.0);
+    }
+}
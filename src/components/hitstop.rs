@@ -1,8 +1,9 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Component that freezes an entity during hit impact
 /// Applied to both attacker and defender when an attack connects
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Hitstop {
     /// Frames remaining in freeze
     pub frames_remaining: u32,
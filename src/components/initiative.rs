@@ -1,8 +1,9 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Initiative tracks frame advantage/disadvantage
 /// Positive = advantage (can act first), Negative = disadvantage (must wait)
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Initiative {
     /// Current frame advantage
     /// Positive = can act first, Negative = must wait
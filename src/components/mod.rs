@@ -10,6 +10,7 @@ pub mod health;
 pub mod hitstop;
 pub mod initiative;
 pub mod movelist;
+pub mod stance;
 pub mod state;
 pub mod stumble;
 
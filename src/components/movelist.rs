@@ -1,10 +1,17 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::components::state::AttackType;
+use crate::components::state::{AttackPhase, AttackType};
 use crate::components::combat::{AttackProperty, StumbleProperty};
+use crate::components::guard::ParryData;
+use crate::components::stance::StanceData;
+use crate::systems::snapshot::fnv1a;
+
+/// Character both players use until there is a character select (recorded in replays)
+pub const DEFAULT_CHARACTER: &str = "default";
 
 /// Direction of directional attack input
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AttackDirection {
     /// No direction held (standing attack)
     Neutral,
@@ -14,6 +21,10 @@ pub enum AttackDirection {
     Down,
     /// Back (away from opponent)
     Back,
+    /// In the air; the aerial attack, only ever used while jumping
+    Air,
+    /// Released from a stance; only ever used from one
+    Stance,
 }
 
 impl Default for AttackDirection {
@@ -36,7 +47,7 @@ impl MoveId {
 }
 
 /// Movement properties for attacks that move the character
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct AttackMovement {
     /// Distance to move (positive = forward, negative = backward)
     pub distance: f32,
@@ -44,24 +55,50 @@ pub struct AttackMovement {
     pub speed: f32,
 }
 
-impl AttackMovement {
-    pub fn new(distance: f32, speed: f32) -> Self {
-        Self { distance, speed }
+/// Rectangle relative to a fighter's centre, authored facing right
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoxShape {
+    pub offset: Vec2,
+    pub size: Vec2,
+}
+
+impl BoxShape {
+    /// Local-space rect for a fighter facing right (`facing_sign` 1.0) or left (-1.0)
+    pub fn rect(&self, facing_sign: f32) -> Rect {
+        Rect::from_center_size(Vec2::new(self.offset.x * facing_sign, self.offset.y), self.size)
     }
+}
+
+/// One hitbox of a move, out on some of its active frames
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HitboxShape {
+    pub offset: Vec2,
+    pub size: Vec2,
+    /// First active frame the box is out (0 = first active frame)
+    #[serde(default)]
+    pub from_frame: u32,
+    /// Active frame the box is gone by, `None` for the rest of the active phase
+    #[serde(default)]
+    pub until_frame: Option<u32>,
+}
 
-    /// Forward advancing movement
-    pub fn forward(distance: f32) -> Self {
-        Self::new(distance, 15.0)
+impl HitboxShape {
+    /// Whether the box is out on active frame `frame`
+    pub fn is_out(&self, frame: u32) -> bool {
+        frame >= self.from_frame && self.until_frame.is_none_or(|until| frame < until)
     }
 
-    /// Backward retreating movement
-    pub fn back(distance: f32) -> Self {
-        Self::new(-distance, 15.0)
+    pub fn rect(&self, facing_sign: f32) -> Rect {
+        BoxShape { offset: self.offset, size: self.size }.rect(facing_sign)
     }
 }
 
 /// Complete definition of a single move
-#[derive(Debug, Clone)]
+///
+/// Loaded from character files (see `data::character`); list fields,
+/// `movement`, `hurtbox` and `stumble_property` may be left out and default
+/// to none.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MoveData {
     /// Display name of the move
     pub name: String,
@@ -77,21 +114,37 @@ pub struct MoveData {
     // Damage & advantage
     /// Base damage dealt
     pub damage: f32,
+    /// Frame advantage on hit, once both fighters recover
+    pub on_hit: i32,
     /// Frame advantage on block (negative = disadvantage)
     pub on_block: i32,
+    /// Damage dealt through a block
+    pub chip_damage: f32,
+    /// Guard meter filled on block (1.0 = a full meter)
+    pub guard_damage: f32,
 
     // Hitbox definition
     /// Offset from character position (local space)
     pub hitbox_offset: Vec2,
     /// Size of the hitbox
     pub hitbox_size: Vec2,
+    /// Hitboxes replacing `hitbox_offset`/`hitbox_size` when set: several
+    /// boxes, or boxes that change over the active frames
+    #[serde(default)]
+    pub hitboxes: Vec<HitboxShape>,
+    /// Hurtbox while performing the move (e.g. crouching for lows);
+    /// the fighter's standing hurtbox when `None`
+    #[serde(default)]
+    pub hurtbox: Option<BoxShape>,
 
     // Properties
     /// Special properties (armor, unblockable, etc.)
+    #[serde(default)]
     pub properties: Vec<AttackProperty>,
 
     // Movement
     /// Optional movement during attack
+    #[serde(default)]
     pub movement: Option<AttackMovement>,
 
     // Hitstop (freeze frames on hit)
@@ -104,18 +157,23 @@ pub struct MoveData {
 
     // Cancel system (combo routes)
     /// What attack types this move can cancel into on hit
+    #[serde(default)]
     pub cancellable_into: Vec<AttackType>,
     /// How many frames during recovery allow canceling
+    #[serde(default)]
     pub cancel_window_frames: u32,
 
     // Counter hit cancel system
     /// Additional attack types this move can cancel into on COUNTER HIT only
+    #[serde(default)]
     pub counter_cancellable_into: Vec<AttackType>,
     /// Cancel window frames on counter hit (typically longer than normal)
+    #[serde(default)]
     pub counter_cancel_window_frames: u32,
 
     // Stumble/juggling system
     /// Stumble property (None, Launcher, Extender, or Spike)
+    #[serde(default)]
     pub stumble_property: StumbleProperty,
 }
 
@@ -125,10 +183,41 @@ impl MoveData {
         self.startup_frames + self.active_frames + self.recovery_frames
     }
 
-    /// Get hitstun frames (for damage system)
-    pub fn hitstun_frames(&self) -> u32 {
-        // Rough formula: more damage = more hitstun
-        (self.damage * 2.0) as u32 + 10
+    /// Local-space hitbox rects on active frame `frame` (0 = first active frame)
+    pub fn hitbox_rects(&self, frame: u32, facing_sign: f32) -> Vec<Rect> {
+        if self.hitboxes.is_empty() {
+            let shape = BoxShape { offset: self.hitbox_offset, size: self.hitbox_size };
+            return vec![shape.rect(facing_sign)];
+        }
+        self.hitboxes
+            .iter()
+            .filter(|shape| shape.is_out(frame))
+            .map(|shape| shape.rect(facing_sign))
+            .collect()
+    }
+
+    /// Frames spent in `phase`
+    pub fn phase_frames(&self, phase: AttackPhase) -> u32 {
+        match phase {
+            AttackPhase::Startup => self.startup_frames,
+            AttackPhase::Active => self.active_frames,
+            AttackPhase::Recovery => self.recovery_frames,
+        }
+    }
+
+    /// Frames from the connecting frame until the attacker is back to Idle,
+    /// connecting `active_elapsed` frames into the active phase (0 = the
+    /// first active frame)
+    pub fn frames_left_after(&self, active_elapsed: u32) -> u32 {
+        self.active_frames.saturating_sub(active_elapsed) + self.recovery_frames
+    }
+
+    /// Hitstun or blockstun, in frames from the connecting frame until the
+    /// defender is free, sized so the attacker is back to Idle `on_hit` /
+    /// `on_block` frames before the defender (at least 1 frame of stun)
+    pub fn stun_frames(&self, active_elapsed: u32, blocked: bool) -> u32 {
+        let advantage = if blocked { self.on_block } else { self.on_hit };
+        (self.frames_left_after(active_elapsed) as i32 + advantage).max(1) as u32
     }
 
     /// Get appropriate hitstop based on hit type
@@ -144,9 +233,14 @@ impl MoveData {
 }
 
 /// Component that holds a character's complete movelist
+///
+/// Also carries the character's parry tuning and stances, so all swap together on
+/// hot reload.
 #[derive(Component, Debug, Clone)]
 pub struct Movelist {
     moves: HashMap<MoveId, MoveData>,
+    parry: ParryData,
+    stances: Vec<StanceData>,
 }
 
 impl Movelist {
@@ -154,9 +248,31 @@ impl Movelist {
     pub fn new() -> Self {
         Self {
             moves: HashMap::new(),
+            parry: ParryData::default(),
+            stances: Vec::new(),
         }
     }
 
+    /// Parry timing and rewards
+    pub fn parry(&self) -> &ParryData {
+        &self.parry
+    }
+
+    /// Replace the parry tuning
+    pub fn set_parry(&mut self, parry: ParryData) {
+        self.parry = parry;
+    }
+
+    /// Stance entered by holding `button`, if the character has one
+    pub fn stance(&self, button: AttackType) -> Option<&StanceData> {
+        self.stances.iter().find(|stance| stance.button == button)
+    }
+
+    /// Replace the stances
+    pub fn set_stances(&mut self, stances: Vec<StanceData>) {
+        self.stances = stances;
+    }
+
     /// Add a move to the movelist
     pub fn add_move(&mut self, attack_type: AttackType, direction: AttackDirection, move_data: MoveData) {
         let move_id = MoveId::new(attack_type, direction);
@@ -169,260 +285,77 @@ impl Movelist {
         self.moves.get(&move_id)
     }
 
+    /// Get move data by id
+    pub fn get(&self, move_id: MoveId) -> Option<&MoveData> {
+        self.moves.get(&move_id)
+    }
+
+    /// Move performed by `attack_type` in `direction`
+    ///
+    /// Directions without a move of their own fall back to the neutral move,
+    /// except `Air` and `Stance`: in the air there is only the aerial attack,
+    /// and a stance only releases its own move.
+    pub fn resolve(&self, attack_type: AttackType, direction: AttackDirection) -> Option<MoveId> {
+        let own_move_only = matches!(direction, AttackDirection::Air | AttackDirection::Stance);
+        let fallback = (!own_move_only).then_some(AttackDirection::Neutral);
+        [Some(direction), fallback]
+            .into_iter()
+            .flatten()
+            .map(|direction| MoveId::new(attack_type, direction))
+            .find(|move_id| self.moves.contains_key(move_id))
+    }
+
     /// Check if a move exists
     pub fn has_move(&self, attack_type: AttackType, direction: AttackDirection) -> bool {
         let move_id = MoveId::new(attack_type, direction);
         self.moves.contains_key(&move_id)
     }
 
-    /// Create a default character movelist with all 8 directional attacks
-    pub fn default_character() -> Self {
-        let mut movelist = Self::new();
-
-        // === LIGHT ATTACKS ===
-
-        // Neutral Light: Standard jab
-        movelist.add_move(
-            AttackType::Light,
-            AttackDirection::Neutral,
-            MoveData {
-                name: "Jab".to_string(),
-                startup_frames: 5,  // Reduced from 6
-                active_frames: 2,
-                recovery_frames: 10,
-                damage: 8.0,
-                on_block: -2,
-                hitbox_offset: Vec2::new(50.0, 0.0),  // Scaled by 1.25x
-                hitbox_size: Vec2::new(150.0, 119.0),  // Scaled by 1.25x (1.5x character width)
-                properties: vec![],
-                movement: None,
-                hitstop_on_hit: 9,
-                hitstop_on_block: 6,
-                hitstop_on_counter: 12,
-                cancellable_into: vec![AttackType::Light, AttackType::Heavy, AttackType::Grab],
-                cancel_window_frames: 7,  // Cancel during first 7 frames of 10f recovery
-                counter_cancellable_into: vec![],  // No additional cancels on counter (already has all)
-                counter_cancel_window_frames: 8,  // Slightly extended window on counter hit
-                stumble_property: StumbleProperty::Extender(crate::components::stumble::StumbleDirection::Backward, 15),
-            },
-        );
-
-        // Forward Light: Fast advancing poke
-        movelist.add_move(
-            AttackType::Light,
-            AttackDirection::Forward,
-            MoveData {
-                name: "Dash Jab".to_string(),
-                startup_frames: 3,  // Reduced from 4
-                active_frames: 2,
-                recovery_frames: 10,
-                damage: 6.0,
-                on_block: -2,
-                hitbox_offset: Vec2::new(62.5, 0.0),  // Scaled by 1.25x
-                hitbox_size: Vec2::new(163.0, 119.0),  // Scaled by 1.25x (1.6x character width, lunging)
-                properties: vec![],
-                movement: Some(AttackMovement::forward(50.0)),
-                hitstop_on_hit: 8,
-                hitstop_on_block: 6,
-                hitstop_on_counter: 11,
-                cancellable_into: vec![AttackType::Light, AttackType::Heavy, AttackType::Grab],
-                cancel_window_frames: 7,  // Cancel during first 7 frames of 10f recovery
-                counter_cancellable_into: vec![],  // No additional cancels on counter (already has all)
-                counter_cancel_window_frames: 8,  // Slightly extended window on counter hit
-                stumble_property: StumbleProperty::Extender(crate::components::stumble::StumbleDirection::Forward, 15),
-            },
-        );
-
-        // Down Light: Low poke
-        movelist.add_move(
-            AttackType::Light,
-            AttackDirection::Down,
-            MoveData {
-                name: "Low Poke".to_string(),
-                startup_frames: 5,  // Reduced from 7
-                active_frames: 2,
-                recovery_frames: 11,
-                damage: 7.0,
-                on_block: -3,
-                hitbox_offset: Vec2::new(50.0, -37.5),  // Lower hitbox, scaled by 1.25x
-                hitbox_size: Vec2::new(156.0, 81.0),  // Scaled by 1.25x (1.5x character width, low)
-                properties: vec![],
-                movement: None,
-                hitstop_on_hit: 8,
-                hitstop_on_block: 6,
-                hitstop_on_counter: 11,
-                cancellable_into: vec![AttackType::Light, AttackType::Heavy],  // No Grab cancel (already low)
-                cancel_window_frames: 6,  // Slightly tighter window
-                counter_cancellable_into: vec![],  // No additional cancels on counter
-                counter_cancel_window_frames: 7,  // Slightly extended window on counter hit
-                stumble_property: StumbleProperty::Extender(crate::components::stumble::StumbleDirection::Down, 12),
-            },
-        );
-
-        // Back Light: Safe retreating jab
-        movelist.add_move(
-            AttackType::Light,
-            AttackDirection::Back,
-            MoveData {
-                name: "Step Jab".to_string(),
-                startup_frames: 4,  // Reduced from 5
-                active_frames: 2,
-                recovery_frames: 9,
-                damage: 6.0,
-                on_block: 1,  // Positive on block (safe)
-                hitbox_offset: Vec2::new(44.0, 0.0),  // Scaled by 1.25x
-                hitbox_size: Vec2::new(144.0, 119.0),  // Scaled by 1.25x (1.4x character width, defensive)
-                properties: vec![],
-                movement: Some(AttackMovement::back(30.0)),
-                hitstop_on_hit: 8,
-                hitstop_on_block: 6,
-                hitstop_on_counter: 11,
-                cancellable_into: vec![],  // Defensive move, no cancels (reset to neutral)
-                cancel_window_frames: 0,  // No cancel window
-                counter_cancellable_into: vec![],  // Still no cancels even on counter (defensive reset)
-                counter_cancel_window_frames: 0,
-                stumble_property: StumbleProperty::None,  // Defensive reset, no stumble
-            },
-        );
-
-        // === HEAVY ATTACKS ===
-
-        // Neutral Heavy: Standard power hit
-        movelist.add_move(
-            AttackType::Heavy,
-            AttackDirection::Neutral,
-            MoveData {
-                name: "Heavy Strike".to_string(),
-                startup_frames: 11,  // Reduced from 14
-                active_frames: 4,
-                recovery_frames: 18,
-                damage: 15.0,
-                on_block: -8,
-                hitbox_offset: Vec2::new(62.5, 0.0),  // Scaled by 1.25x
-                hitbox_size: Vec2::new(213.0, 163.0),  // Scaled by 1.25x (2.1x character width)
-                properties: vec![AttackProperty::LightArmor],
-                movement: None,
-                hitstop_on_hit: 13,
-                hitstop_on_block: 10,
-                hitstop_on_counter: 16,
-                cancellable_into: vec![],  // Heavy attacks are committal, no normal cancels
-                cancel_window_frames: 0,
-                counter_cancellable_into: vec![AttackType::Light, AttackType::Heavy],  // Can cancel to Light/Heavy on COUNTER HIT
-                counter_cancel_window_frames: 7,  // Generous 7-frame window as reward
-                stumble_property: StumbleProperty::Launcher(crate::components::stumble::StumbleDirection::Backward, 30),
-            },
-        );
-
-        // Forward Heavy: Advancing overhead
-        movelist.add_move(
-            AttackType::Heavy,
-            AttackDirection::Forward,
-            MoveData {
-                name: "Lunging Strike".to_string(),
-                startup_frames: 9,  // Reduced from 12
-                active_frames: 4,
-                recovery_frames: 18,
-                damage: 13.0,
-                on_block: -6,
-                hitbox_offset: Vec2::new(75.0, 12.5),  // Slightly higher, scaled by 1.25x
-                hitbox_size: Vec2::new(238.0, 163.0),  // Scaled by 1.25x (2.4x character width, lunging)
-                properties: vec![],
-                movement: Some(AttackMovement::forward(80.0)),
-                hitstop_on_hit: 12,
-                hitstop_on_block: 9,
-                hitstop_on_counter: 15,
-                cancellable_into: vec![],  // Heavy attacks are committal, no normal cancels
-                cancel_window_frames: 0,
-                counter_cancellable_into: vec![AttackType::Light, AttackType::Heavy],  // Can cancel to Light/Heavy on COUNTER HIT
-                counter_cancel_window_frames: 7,  // Generous 7-frame window as reward
-                stumble_property: StumbleProperty::Launcher(crate::components::stumble::StumbleDirection::Forward, 28),
-            },
-        );
-
-        // Down Heavy: Low sweep
-        movelist.add_move(
-            AttackType::Heavy,
-            AttackDirection::Down,
-            MoveData {
-                name: "Sweep".to_string(),
-                startup_frames: 13,  // Reduced from 16
-                active_frames: 4,
-                recovery_frames: 20,
-                damage: 16.0,
-                on_block: -10,
-                hitbox_offset: Vec2::new(62.5, -43.75),  // Low hitbox, scaled by 1.25x
-                hitbox_size: Vec2::new(250.0, 63.0),  // Scaled by 1.25x (2.5x character width, sweep)
-                properties: vec![],
-                movement: None,
-                hitstop_on_hit: 14,
-                hitstop_on_block: 11,
-                hitstop_on_counter: 17,
-                cancellable_into: vec![],  // Heavy attacks are committal, no normal cancels
-                cancel_window_frames: 0,
-                counter_cancellable_into: vec![AttackType::Light, AttackType::Heavy],  // Can cancel to Light/Heavy on COUNTER HIT
-                counter_cancel_window_frames: 7,  // Generous 7-frame window as reward
-                stumble_property: StumbleProperty::Launcher(crate::components::stumble::StumbleDirection::Down, 25),
-            },
-        );
-
-        // Back Heavy: Defensive power hit
-        movelist.add_move(
-            AttackType::Heavy,
-            AttackDirection::Back,
-            MoveData {
-                name: "Counter Strike".to_string(),
-                startup_frames: 10,  // Reduced from 13
-                active_frames: 4,
-                recovery_frames: 16,
-                damage: 14.0,
-                on_block: -4,  // Safer than normal heavy
-                hitbox_offset: Vec2::new(56.0, 0.0),  // Scaled by 1.25x
-                hitbox_size: Vec2::new(200.0, 163.0),  // Scaled by 1.25x (2.0x character width, defensive)
-                properties: vec![],
-                movement: Some(AttackMovement::back(40.0)),
-                hitstop_on_hit: 13,
-                hitstop_on_block: 10,
-                hitstop_on_counter: 16,
-                cancellable_into: vec![],  // Heavy attacks are committal, no normal cancels
-                cancel_window_frames: 0,
-                counter_cancellable_into: vec![AttackType::Light, AttackType::Heavy],  // Can cancel to Light/Heavy on COUNTER HIT
-                counter_cancel_window_frames: 7,  // Generous 7-frame window as reward
-                stumble_property: StumbleProperty::Launcher(crate::components::stumble::StumbleDirection::Backward, 32),
-            },
-        );
-
-        // === GRAB (Neutral only for now) ===
-        movelist.add_move(
-            AttackType::Grab,
-            AttackDirection::Neutral,
-            MoveData {
-                name: "Grab".to_string(),
-                startup_frames: 10,
-                active_frames: 2,
-                recovery_frames: 20,
-                damage: 12.0,
-                on_block: 0,
-                hitbox_offset: Vec2::new(44.0, 0.0),  // Scaled by 1.25x
-                hitbox_size: Vec2::new(150.0, 150.0),  // Scaled by 1.25x (1.5x character width, square, very generous)
-                properties: vec![AttackProperty::Unblockable],
-                movement: None,
-                hitstop_on_hit: 11,
-                hitstop_on_block: 0,  // Can't be blocked
-                hitstop_on_counter: 14,
-                cancellable_into: vec![],  // Grab is not cancellable
-                cancel_window_frames: 0,
-                counter_cancellable_into: vec![],  // Still not cancellable even on counter hit
-                counter_cancel_window_frames: 0,
-                stumble_property: StumbleProperty::None,  // Grab doesn't cause stumble (different purpose)
-            },
-        );
-
-        movelist
+    /// Names of moves that differ in `other` (added, removed or edited), sorted
+    pub fn changed_moves(&self, other: &Movelist) -> Vec<String> {
+        let mut changed: Vec<String> = self
+            .moves
+            .iter()
+            .filter(|(id, data)| other.moves.get(*id) != Some(*data))
+            .map(|(_, data)| data.name.clone())
+            .chain(
+                other
+                    .moves
+                    .iter()
+                    .filter(|(id, _)| !self.moves.contains_key(*id))
+                    .map(|(_, data)| data.name.clone()),
+            )
+            .collect();
+        if self.parry != other.parry {
+            changed.push("parry".to_string());
+        }
+        if self.stances != other.stances {
+            changed.push("stances".to_string());
+        }
+        changed.sort();
+        changed.dedup();
+        changed
+    }
+
+    /// Fingerprint of every move's data, the parry tuning and the stances,
+    /// to tell movelist revisions apart
+    ///
+    /// Moves are sorted first because `HashMap` iteration order varies per run.
+    pub fn fingerprint(&self) -> u64 {
+        let mut entries: Vec<String> = self
+            .moves
+            .iter()
+            .map(|(id, data)| format!("{:?} {:?}", id, data))
+            .collect();
+        entries.sort();
+        entries.push(format!("{:?}", self.parry));
+        entries.push(format!("{:?}", self.stances));
+        fnv1a(entries.concat().as_bytes())
     }
 }
 
 impl Default for Movelist {
     fn default() -> Self {
-        Self::default_character()
+        Self::new()
     }
 }
@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use crate::components::state::AttackType;
+
+/// A character's stance (Aji), from its character file
+///
+/// Entered by holding `button` through the first `entry_frames` of that
+/// button's attack. Letting go releases the stance move (the movelist's
+/// `Stance` direction for the same button); Block cancels back to neutral.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StanceData {
+    /// Display name
+    pub name: String,
+    /// Attack button held to enter, one stance per button
+    pub button: AttackType,
+    /// Frames of the attack's startup the button must be held to enter
+    pub entry_frames: u32,
+    /// Guard meter filled per second while held (1.0 = a full meter)
+    pub guard_drain: f32,
+    /// Initiative lost cancelling the stance
+    pub cancel_cost: i32,
+    /// Guard damage taken blocking from the stance; only with `guards`
+    #[serde(default = "full")]
+    pub guard_damage_multiplier: f32,
+    /// Walk speed in the stance, as a fraction of normal (0 = planted)
+    #[serde(default)]
+    pub walk_speed_multiplier: f32,
+    /// Whether the stance blocks like holding block does
+    #[serde(default)]
+    pub guards: bool,
+}
+
+fn full() -> f32 {
+    1.0
+}
@@ -1,7 +1,8 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Type of attack being performed
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AttackType {
     Light,
     Heavy,
@@ -9,7 +10,7 @@ pub enum AttackType {
 }
 
 /// Phase of an attack animation
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AttackPhase {
     /// Windup before hitbox becomes active
     Startup,
@@ -19,10 +20,10 @@ pub enum AttackPhase {
     Recovery,
 }
 
-use crate::components::movelist::AttackDirection;
+use crate::components::movelist::{AttackDirection, MoveId};
 
 /// Character state machine
-#[derive(Component, Debug, Clone, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CharacterState {
     Idle,
     Walking,
@@ -36,10 +37,90 @@ pub enum CharacterState {
     },
     /// Holding block
     Blocking,
-    /// Attempting a parry
-    Parrying { frames_remaining: u32 },
-    /// Knocked back, unable to act
-    Staggered { frames_remaining: u32 },
+    /// Attempting a parry; `elapsed` counts frames into the window, for grading
+    Parrying { frames_remaining: u32, elapsed: u32 },
+    /// Reeling from a hit, unable to act (see `MoveData::stun_frames`)
+    Hitstun { frames_remaining: u32 },
+    /// Held in block by a blocked hit; blocks further hits meanwhile
+    Blockstun { frames_remaining: u32 },
+    /// Knocked back, unable to act; `source` sets the stagger's severity
+    Staggered { frames_remaining: u32, source: StaggerSource },
+    /// Crouching to jump, still on the ground
+    Prejump { frames_remaining: u32 },
+    /// In the jump arc (see `jump::JumpArc`), free only to use the aerial attack
+    Airborne,
+    /// Touched down from a jump, unable to act
+    Landing { frames_remaining: u32 },
+    /// Holding the stance entered with `button` (see `stance::StanceData`)
+    Stance { button: AttackType },
+    /// Winding up the Decisive Blow; the stroke resolves when this reaches 0,
+    /// and a fighter whose stroke killed holds it until the round resets
+    DecisiveBlow { frames_remaining: u32 },
+}
+
+impl CharacterState {
+    /// Move being performed, if attacking
+    pub fn move_id(&self) -> Option<MoveId> {
+        match self {
+            CharacterState::Attacking { attack_type, direction, .. } => Some(MoveId::new(*attack_type, *direction)),
+            _ => None,
+        }
+    }
+
+    /// Stagger for `frames` from `source`
+    pub fn staggered(source: StaggerSource, frames: u32) -> Self {
+        CharacterState::Staggered {
+            frames_remaining: frames,
+            source,
+        }
+    }
+
+    /// Severity of the stagger, if staggered
+    pub fn stagger_severity(&self) -> Option<StaggerSeverity> {
+        match self {
+            CharacterState::Staggered { source, .. } => Some(source.severity()),
+            _ => None,
+        }
+    }
+}
+
+/// How badly a fighter is staggered; only Medium and Heavy open a Decisive Blow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum StaggerSeverity {
+    Light,
+    Medium,
+    Heavy,
+}
+
+impl StaggerSeverity {
+    pub fn opens_decisive_blow(&self) -> bool {
+        *self >= StaggerSeverity::Medium
+    }
+}
+
+/// What put a fighter into stagger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StaggerSource {
+    /// Hit during the recovery of a whiffed attack
+    WhiffPunish,
+    /// Caught by a grab
+    Grab,
+    /// Attack Perfect parried (see `guard::ParryData::punish_frames`)
+    Parry,
+    /// Guard meter filled
+    GuardBreak,
+    /// Whiffed, or was cut down by, a Decisive Blow
+    DecisiveBlow,
+}
+
+impl StaggerSource {
+    pub fn severity(&self) -> StaggerSeverity {
+        match self {
+            StaggerSource::WhiffPunish | StaggerSource::Parry => StaggerSeverity::Light,
+            StaggerSource::Grab => StaggerSeverity::Medium,
+            StaggerSource::GuardBreak | StaggerSource::DecisiveBlow => StaggerSeverity::Heavy,
+        }
+    }
 }
 
 impl Default for CharacterState {
@@ -49,7 +130,7 @@ impl Default for CharacterState {
 }
 
 /// Frame timer for state transitions
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct StateTimer {
     pub elapsed: u32,
     pub target: u32,
@@ -73,66 +154,3 @@ impl StateTimer {
         self.target = new_target;
     }
 }
-
-/// Frame data for an attack move
-#[derive(Component, Debug, Clone)]
-pub struct AttackData {
-    pub attack_type: AttackType,
-    /// Frames before hitbox becomes active
-    pub startup_frames: u32,
-    /// Frames hitbox remains active
-    pub active_frames: u32,
-    /// Frames of recovery after hitbox deactivates
-    pub recovery_frames: u32,
-    /// Damage dealt (in health states)
-    pub damage: u8,
-    /// Frame advantage on block (negative = disadvantage)
-    pub on_block: i32,
-}
-
-impl AttackData {
-    /// Light attack frame data: 6f startup, 2f active, 10f recovery
-    pub fn light() -> Self {
-        Self {
-            attack_type: AttackType::Light,
-            startup_frames: 6,
-            active_frames: 2,
-            recovery_frames: 10,
-            damage: 1,
-            on_block: -2,
-        }
-    }
-
-    /// Heavy attack frame data: 14f startup, 4f active, 18f recovery
-    pub fn heavy() -> Self {
-        Self {
-            attack_type: AttackType::Heavy,
-            startup_frames: 14,
-            active_frames: 4,
-            recovery_frames: 18,
-            damage: 2,
-            on_block: -8,
-        }
-    }
-
-    /// Grab frame data: 10f startup, 2f active, 20f recovery (whiff)
-    pub fn grab() -> Self {
-        Self {
-            attack_type: AttackType::Grab,
-            startup_frames: 10,
-            active_frames: 2,
-            recovery_frames: 20,
-            damage: 0, // Grab doesn't deal direct damage
-            on_block: 0,
-        }
-    }
-
-    /// Get the total frame duration based on current phase
-    pub fn phase_duration(&self, phase: AttackPhase) -> u32 {
-        match phase {
-            AttackPhase::Startup => self.startup_frames,
-            AttackPhase::Active => self.active_frames,
-            AttackPhase::Recovery => self.recovery_frames,
-        }
-    }
-}
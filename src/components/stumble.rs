@@ -1,7 +1,8 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Direction of stumble/tumble
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StumbleDirection {
     /// Stumble backward (away from attacker)
     Backward,
@@ -18,7 +19,7 @@ pub enum StumbleDirection {
 /// - Attacker can extend with directional hits
 /// - Wall bounces create big opportunities
 /// - Spike finishers end the juggle
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct StumbleState {
     /// Total frames of stumble (includes extensions)
     pub frames_remaining: u32,
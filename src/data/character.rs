@@ -0,0 +1,299 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+use std::fmt;
+use crate::components::guard::ParryData;
+use crate::components::movelist::{AttackDirection, MoveData, Movelist};
+use crate::components::stance::StanceData;
+use crate::components::state::AttackType;
+
+/// Folder character files are loaded from, relative to `assets/`
+pub const CHARACTER_DIR: &str = "data/characters";
+
+/// File extension of character files (`<name>.character.ron`)
+pub const CHARACTER_EXTENSION: &str = "character.ron";
+
+/// Asset path of a character file
+pub fn character_path(name: &str) -> String {
+    format!("{}/{}.{}", CHARACTER_DIR, name, CHARACTER_EXTENSION)
+}
+
+/// One move in a character file
+#[derive(Debug, Clone, Deserialize)]
+pub struct MoveEntry {
+    pub attack: AttackType,
+    pub direction: AttackDirection,
+    pub data: MoveData,
+}
+
+/// Character definition loaded from `assets/data/characters/<name>.character.ron`
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct CharacterData {
+    pub name: String,
+    pub moves: Vec<MoveEntry>,
+    /// Parry timing and rewards; defaults when left out
+    #[serde(default)]
+    pub parry: ParryData,
+    /// Stances (Aji), at most one per attack button
+    #[serde(default)]
+    pub stances: Vec<StanceData>,
+}
+
+impl CharacterData {
+    /// Parse and validate a character file
+    pub fn parse(text: &str) -> Result<Self, CharacterError> {
+        let character: CharacterData = ron::from_str(text).map_err(|e| CharacterError::Parse(e.to_string()))?;
+        character.validate()?;
+        Ok(character)
+    }
+
+    /// Reject files that would load but can't play: duplicate or frameless
+    /// moves, chip above full damage, guard damage outside 0..=1, hitboxes
+    /// that are never out, a parry whose Perfect frames overrun its window or
+    /// a stance that can't be entered
+    fn validate(&self) -> Result<(), CharacterError> {
+        for (index, entry) in self.moves.iter().enumerate() {
+            let id = (entry.attack, entry.direction);
+            if self.moves[..index].iter().any(|other| (other.attack, other.direction) == id) {
+                return Err(CharacterError::Invalid(format!(
+                    "{:?} {:?} is defined more than once",
+                    entry.direction, entry.attack
+                )));
+            }
+            if entry.data.startup_frames == 0 || entry.data.active_frames == 0 {
+                return Err(CharacterError::Invalid(format!(
+                    "'{}' needs at least one startup and one active frame",
+                    entry.data.name
+                )));
+            }
+            if entry.data.chip_damage < 0.0 || entry.data.chip_damage > entry.data.damage {
+                return Err(CharacterError::Invalid(format!(
+                    "'{}' chip_damage {} must be between 0 and its damage {}",
+                    entry.data.name, entry.data.chip_damage, entry.data.damage
+                )));
+            }
+            if !(0.0..=1.0).contains(&entry.data.guard_damage) {
+                return Err(CharacterError::Invalid(format!(
+                    "'{}' guard_damage must be between 0 and 1, got {}",
+                    entry.data.name, entry.data.guard_damage
+                )));
+            }
+            let active_frames = entry.data.active_frames;
+            if let Some(shape) = entry.data.hitboxes.iter().find(|shape| {
+                (0..active_frames).all(|frame| !shape.is_out(frame))
+            }) {
+                return Err(CharacterError::Invalid(format!(
+                    "'{}' has a hitbox outside its {} active frames: {:?}",
+                    entry.data.name, active_frames, shape
+                )));
+            }
+        }
+        if self.parry.window_frames == 0 || self.parry.perfect_frames > self.parry.window_frames {
+            return Err(CharacterError::Invalid(format!(
+                "parry perfect_frames {} must fit in a window of at least one frame, got {}",
+                self.parry.perfect_frames, self.parry.window_frames
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.parry.guard_restore) {
+            return Err(CharacterError::Invalid(format!(
+                "parry guard_restore must be between 0 and 1, got {}",
+                self.parry.guard_restore
+            )));
+        }
+        for (index, stance) in self.stances.iter().enumerate() {
+            if self.stances[..index].iter().any(|other| other.button == stance.button) {
+                return Err(CharacterError::Invalid(format!(
+                    "more than one stance on {:?}",
+                    stance.button
+                )));
+            }
+            // Entered during the startup of the button's neutral move
+            let startup = self
+                .moves
+                .iter()
+                .find(|entry| entry.attack == stance.button && entry.direction == AttackDirection::Neutral)
+                .map(|entry| entry.data.startup_frames);
+            if stance.entry_frames == 0 || startup.is_none_or(|startup| stance.entry_frames >= startup) {
+                return Err(CharacterError::Invalid(format!(
+                    "stance '{}' entry_frames {} must fit in the startup of the neutral {:?} ({:?})",
+                    stance.name, stance.entry_frames, stance.button, startup
+                )));
+            }
+            if !(0.0..=1.0).contains(&stance.guard_drain)
+                || !(0.0..=1.0).contains(&stance.walk_speed_multiplier)
+                || stance.guard_damage_multiplier < 0.0
+                || stance.cancel_cost < 0
+            {
+                return Err(CharacterError::Invalid(format!(
+                    "stance '{}' needs guard_drain and walk_speed_multiplier between 0 and 1, \
+                     and no negative guard_damage_multiplier or cancel_cost",
+                    stance.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the movelist fighters spawn with
+    pub fn movelist(&self) -> Movelist {
+        let mut movelist = Movelist::new();
+        for entry in &self.moves {
+            movelist.add_move(entry.attack, entry.direction, entry.data.clone());
+        }
+        movelist.set_parry(self.parry.clone());
+        movelist.set_stances(self.stances.clone());
+        movelist
+    }
+}
+
+#[derive(Debug)]
+pub enum CharacterError {
+    Io(std::io::Error),
+    Parse(String),
+    Invalid(String),
+}
+
+impl fmt::Display for CharacterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CharacterError::Io(e) => write!(f, "{}", e),
+            CharacterError::Parse(e) => write!(f, "invalid character file: {}", e),
+            CharacterError::Invalid(e) => write!(f, "invalid movelist: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CharacterError {}
+
+impl From<std::io::Error> for CharacterError {
+    fn from(e: std::io::Error) -> Self {
+        CharacterError::Io(e)
+    }
+}
+
+/// Loads `*.character.ron` files as `CharacterData`
+#[derive(Default)]
+pub struct CharacterLoader;
+
+impl AssetLoader for CharacterLoader {
+    type Asset = CharacterData;
+    type Settings = ();
+    type Error = CharacterError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<CharacterData, CharacterError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = std::str::from_utf8(&bytes).map_err(|e| CharacterError::Parse(e.to_string()))?;
+        CharacterData::parse(text)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[CHARACTER_EXTENSION]
+    }
+}
+
+/// Every character the game knows, by name
+///
+/// Handles keep the files loaded; movelists are filled in as they finish.
+#[derive(Resource, Debug, Default)]
+pub struct Characters {
+    pub handles: HashMap<String, Handle<CharacterData>>,
+    pub movelists: HashMap<String, Movelist>,
+}
+
+impl Characters {
+    pub fn movelist(&self, name: &str) -> Option<&Movelist> {
+        self.movelists.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT_CHARACTER_FILE: &str = include_str!("../../assets/data/characters/default.character.ron");
+
+    #[test]
+    fn test_default_character_file() {
+        let character = CharacterData::parse(DEFAULT_CHARACTER_FILE).unwrap();
+        let movelist = character.movelist();
+
+        assert_eq!(character.moves.len(), 11);
+        let sweep = movelist.get_move(AttackType::Heavy, AttackDirection::Down).unwrap();
+        assert_eq!(sweep.name, "Sweep");
+        assert_eq!(sweep.startup_frames, 13);
+        assert_eq!(sweep.guard_damage, 0.40);
+        assert!(sweep.hurtbox.is_some());
+        assert!(movelist.get_move(AttackType::Light, AttackDirection::Forward).unwrap().movement.is_some());
+        assert!(!movelist.has_move(AttackType::Grab, AttackDirection::Back));
+        assert_eq!(movelist.stance(AttackType::Heavy).unwrap().name, "Drawn Blade");
+        assert!(movelist.has_move(AttackType::Heavy, AttackDirection::Stance));
+    }
+
+    #[test]
+    fn test_hitboxes_per_active_frame() {
+        let movelist = CharacterData::parse(DEFAULT_CHARACTER_FILE).unwrap().movelist();
+        let sweep = movelist.get_move(AttackType::Heavy, AttackDirection::Down).unwrap();
+
+        let first = sweep.hitbox_rects(0, 1.0);
+        let later = sweep.hitbox_rects(2, 1.0);
+        assert_eq!(first.len(), 1);
+        assert!(later[0].max.x > first[0].max.x);
+
+        // Facing left mirrors the boxes around the fighter's centre
+        let mirrored = sweep.hitbox_rects(2, -1.0);
+        assert_eq!(mirrored[0].min.x, -later[0].max.x);
+
+        let jab = movelist.get_move(AttackType::Light, AttackDirection::Neutral).unwrap();
+        assert_eq!(jab.hitbox_rects(0, 1.0), vec![Rect::from_center_size(jab.hitbox_offset, jab.hitbox_size)]);
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_neutral() {
+        let movelist = CharacterData::parse(DEFAULT_CHARACTER_FILE).unwrap().movelist();
+
+        let sweep = movelist.resolve(AttackType::Heavy, AttackDirection::Down).unwrap();
+        assert_eq!(sweep.direction, AttackDirection::Down);
+        let grab = movelist.resolve(AttackType::Grab, AttackDirection::Back).unwrap();
+        assert_eq!(grab.direction, AttackDirection::Neutral);
+        assert!(Movelist::new().resolve(AttackType::Light, AttackDirection::Neutral).is_none());
+
+        // The aerial attack never falls back to a ground move
+        let aerial = movelist.resolve(AttackType::Heavy, AttackDirection::Air).unwrap();
+        assert_eq!(aerial.direction, AttackDirection::Air);
+        assert!(movelist.resolve(AttackType::Light, AttackDirection::Air).is_none());
+    }
+
+    #[test]
+    fn test_rejects_duplicate_moves() {
+        let text = r#"(name: "dupe", moves: [
+            (attack: Light, direction: Neutral, data: (name: "A", startup_frames: 5, active_frames: 2,
+                recovery_frames: 10, damage: 8.0, on_hit: 4, on_block: -2, chip_damage: 2.0, guard_damage: 0.15, hitbox_offset: (50.0, 0.0),
+                hitbox_size: (100.0, 100.0), hitstop_on_hit: 9, hitstop_on_block: 6, hitstop_on_counter: 12)),
+            (attack: Light, direction: Neutral, data: (name: "B", startup_frames: 5, active_frames: 2,
+                recovery_frames: 10, damage: 8.0, on_hit: 4, on_block: -2, chip_damage: 2.0, guard_damage: 0.15, hitbox_offset: (50.0, 0.0),
+                hitbox_size: (100.0, 100.0), hitstop_on_hit: 9, hitstop_on_block: 6, hitstop_on_counter: 12)),
+        ])"#;
+        assert!(matches!(CharacterData::parse(text), Err(CharacterError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_rejects_stance_it_cant_enter() {
+        let text = |entry_frames: u32| format!(r#"(name: "held", moves: [
+            (attack: Light, direction: Neutral, data: (name: "A", startup_frames: 5, active_frames: 2,
+                recovery_frames: 10, damage: 8.0, on_hit: 4, on_block: -2, chip_damage: 2.0, guard_damage: 0.15, hitbox_offset: (50.0, 0.0),
+                hitbox_size: (100.0, 100.0), hitstop_on_hit: 9, hitstop_on_block: 6, hitstop_on_counter: 12)),
+        ], stances: [(name: "Held", button: Light, entry_frames: {}, guard_drain: 0.03, cancel_cost: 4)])"#, entry_frames);
+
+        assert!(CharacterData::parse(&text(4)).is_ok());
+        // Past the Light's startup the attack is already out
+        assert!(matches!(CharacterData::parse(&text(5)), Err(CharacterError::Invalid(_))));
+    }
+}
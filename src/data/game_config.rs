@@ -1,8 +1,20 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
 use bevy::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::components::state::StaggerSeverity;
+use crate::systems::snapshot::fnv1a;
+
+/// Asset path of the game configuration, relative to `assets/`
+pub const GAME_CONFIG_PATH: &str = "data/game_config.ron";
 
 /// Game configuration loaded from assets/data/game_config.ron
-#[derive(Debug, Clone, Deserialize, Resource)]
+///
+/// Loaded during `GameState::Loading` and inserted as a resource; every
+/// simulation system that needs stage size, speeds, timers or meter tuning
+/// reads it from there. Times are in simulation frames unless noted.
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize, Resource)]
 pub struct GameConfig {
     // Window and rendering
     pub window_width: f32,
@@ -12,20 +24,320 @@ pub struct GameConfig {
     // Stage
     pub stage_width: f32,
     pub stage_height: f32,
+    /// Half a fighter's width, keeps fighters inside the stage edge
+    pub fighter_half_width: f32,
+    /// Distance of each fighter from the centre at round start
+    pub spawn_offset: f32,
+    /// How close to the stage limit a fighter counts as cornered
+    pub corner_depth: f32,
+    /// Pushback of a hit on the defender (the attacker when cornered), in pixels
+    pub hit_pushback: f32,
+    /// Pushback of a blocked hit, in pixels
+    pub block_pushback: f32,
+    /// Pushback speed in pixels per frame
+    pub pushback_speed: f32,
 
     // Movement
+    /// Walk speed in pixels per second
     pub walk_speed: f32,
-    pub step_speed: f32,
-    pub step_duration: u32,
-    pub backdash_speed: f32,
-    pub backdash_duration: u32,
-    pub backdash_iframe_duration: u32,
+    pub dash_distance: f32,
+    /// Dash speed in pixels per frame
+    pub dash_speed: f32,
+    pub dash_cooldown: u32,
+    /// Evade speed in pixels per second
+    pub evade_speed: f32,
+    pub evade_duration: u32,
+    /// Evade i-frames cover frames `evade_invincible_from..evade_invincible_until`
+    pub evade_invincible_from: u32,
+    pub evade_invincible_until: u32,
+
+    // Jump
+    /// Grounded frames before leaving the floor
+    pub jump_prejump_frames: u32,
+    /// Take-off speed in pixels per second, straight up
+    pub jump_velocity: f32,
+    /// Pull back down in pixels per second squared
+    pub jump_gravity: f32,
+    /// Sideways speed in pixels per second, fixed at take-off
+    pub jump_drift_speed: f32,
+    /// Recovery on touching down, before the fighter can act
+    pub jump_landing_frames: u32,
 
     // Frame timing
     pub target_fps: u32,
 
+    // Match
+    pub breaths: u8,
+    pub max_health: f32,
+    pub round_frames: u32,
+    pub countdown_frames: u32,
+
     // Guard
+    /// Guard meter drained per second when not blocking
     pub guard_depletion_rate: f32,
-    pub light_guard_damage: f32,
-    pub heavy_guard_damage: f32,
+
+    // Stagger
+    /// Stagger from a whiff punish
+    pub stagger_light_frames: u32,
+    /// Stagger from a grab
+    pub stagger_medium_frames: u32,
+    /// Stagger from a guard break or a whiffed Decisive Blow
+    pub stagger_heavy_frames: u32,
+
+    // Decisive Blow
+    /// Telegraphed wind-up before the killing stroke
+    pub decisive_windup_frames: u32,
+    /// Parry window against a Decisive Blow (the Final Parry)
+    pub final_parry_frames: u32,
+    /// Horizontal distance the stroke reaches when the wind-up ends
+    pub decisive_reach: f32,
+    /// Freeze on the kill before the round ends
+    pub kill_hold_frames: u32,
+
+    // Pressure and momentum
+    /// Idle frames before pressure drops one level
+    pub pressure_drain_frames: u32,
+    /// Frames without a successful action before momentum starts decaying
+    pub momentum_decay_frames: u32,
+}
+
+impl GameConfig {
+    /// Parse and validate a config file
+    pub fn parse(text: &str) -> Result<Self, GameConfigError> {
+        let config: GameConfig = ron::from_str(text).map_err(|e| GameConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reject values the rules can't run with
+    fn validate(&self) -> Result<(), GameConfigError> {
+        let positive = [
+            ("window_width", self.window_width),
+            ("window_height", self.window_height),
+            ("stage_width", self.stage_width),
+            ("stage_height", self.stage_height),
+            ("fighter_half_width", self.fighter_half_width),
+            ("walk_speed", self.walk_speed),
+            ("dash_distance", self.dash_distance),
+            ("dash_speed", self.dash_speed),
+            ("evade_speed", self.evade_speed),
+            ("jump_velocity", self.jump_velocity),
+            ("jump_gravity", self.jump_gravity),
+            ("max_health", self.max_health),
+            ("decisive_reach", self.decisive_reach),
+            ("pushback_speed", self.pushback_speed),
+        ];
+        for (field, value) in positive {
+            if value.is_nan() || value <= 0.0 {
+                return Err(GameConfigError::Invalid(format!("{} must be positive, got {}", field, value)));
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.guard_depletion_rate) {
+            return Err(GameConfigError::Invalid(format!(
+                "guard_depletion_rate must be between 0 and 1, got {}",
+                self.guard_depletion_rate
+            )));
+        }
+
+        if self.stagger_light_frames == 0
+            || self.stagger_light_frames > self.stagger_medium_frames
+            || self.stagger_medium_frames > self.stagger_heavy_frames
+        {
+            return Err(GameConfigError::Invalid(format!(
+                "stagger frames must be at least 1 and grow from light to heavy, got {}, {} and {}",
+                self.stagger_light_frames, self.stagger_medium_frames, self.stagger_heavy_frames
+            )));
+        }
+
+        if self.breaths == 0 {
+            return Err(GameConfigError::Invalid("breaths must be at least 1".to_string()));
+        }
+        if self.round_frames == 0 || self.target_fps == 0 {
+            return Err(GameConfigError::Invalid("round_frames and target_fps must be at least 1".to_string()));
+        }
+        if self.spawn_offset < 0.0 || self.spawn_offset > self.stage_limit() {
+            return Err(GameConfigError::Invalid(format!(
+                "spawn_offset {} puts fighters outside the stage (limit {})",
+                self.spawn_offset,
+                self.stage_limit()
+            )));
+        }
+        if self.corner_depth < 0.0 || self.corner_depth >= self.stage_limit() {
+            return Err(GameConfigError::Invalid(format!(
+                "corner_depth {} must be between 0 and the stage limit {}",
+                self.corner_depth,
+                self.stage_limit()
+            )));
+        }
+        if self.hit_pushback < 0.0 || self.block_pushback < 0.0 {
+            return Err(GameConfigError::Invalid(format!(
+                "hit_pushback and block_pushback can't be negative, got {} and {}",
+                self.hit_pushback, self.block_pushback
+            )));
+        }
+        if self.final_parry_frames == 0 || self.final_parry_frames >= self.decisive_windup_frames {
+            return Err(GameConfigError::Invalid(format!(
+                "final_parry_frames {} must be at least 1 and shorter than decisive_windup_frames {}",
+                self.final_parry_frames, self.decisive_windup_frames
+            )));
+        }
+        if self.jump_prejump_frames == 0 || self.jump_drift_speed < 0.0 {
+            return Err(GameConfigError::Invalid(format!(
+                "jump_prejump_frames must be at least 1 and jump_drift_speed not negative, got {} and {}",
+                self.jump_prejump_frames, self.jump_drift_speed
+            )));
+        }
+        if self.evade_invincible_from > self.evade_invincible_until || self.evade_invincible_until > self.evade_duration {
+            return Err(GameConfigError::Invalid(format!(
+                "evade i-frames {}..{} must lie within evade_duration {}",
+                self.evade_invincible_from, self.evade_invincible_until, self.evade_duration
+            )));
+        }
+        Ok(())
+    }
+
+    /// Names of the fields whose value differs in `other`, alphabetically
+    pub fn changed_fields(&self, other: &GameConfig) -> Vec<String> {
+        let as_map = |config: &GameConfig| match ron::to_string(config).ok().and_then(|text| ron::from_str(&text).ok()) {
+            Some(ron::Value::Map(map)) => map,
+            _ => ron::Map::new(),
+        };
+        as_map(self)
+            .iter()
+            .zip(as_map(other).iter())
+            .filter(|((_, old), (_, new))| old != new)
+            .filter_map(|((field, _), _)| match field {
+                ron::Value::String(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Stable hash of every value the simulation reads, stored in replays and
+    /// compared in the netplay handshake to tell config revisions apart
+    ///
+    /// Window and rendering settings are left out: they never change a match.
+    pub fn fingerprint(&self) -> u64 {
+        let simulated = GameConfig {
+            window_width: 0.0,
+            window_height: 0.0,
+            clear_color: (0.0, 0.0, 0.0, 0.0),
+            ..self.clone()
+        };
+        fnv1a(format!("{:?}", simulated).as_bytes())
+    }
+
+    /// Length of a stagger of `severity`
+    pub fn stagger_frames(&self, severity: StaggerSeverity) -> u32 {
+        match severity {
+            StaggerSeverity::Light => self.stagger_light_frames,
+            StaggerSeverity::Medium => self.stagger_medium_frames,
+            StaggerSeverity::Heavy => self.stagger_heavy_frames,
+        }
+    }
+
+    /// Furthest a fighter's centre can be from the middle of the stage
+    pub fn stage_limit(&self) -> f32 {
+        self.stage_width / 2.0 - self.fighter_half_width
+    }
+
+    pub fn clear_color(&self) -> Color {
+        let (r, g, b, a) = self.clear_color;
+        Color::srgba(r, g, b, a)
+    }
+}
+
+#[derive(Debug)]
+pub enum GameConfigError {
+    Io(std::io::Error),
+    Parse(String),
+    Invalid(String),
+}
+
+impl fmt::Display for GameConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameConfigError::Io(e) => write!(f, "{}", e),
+            GameConfigError::Parse(e) => write!(f, "invalid game config: {}", e),
+            GameConfigError::Invalid(e) => write!(f, "invalid game config value: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GameConfigError {}
+
+impl From<std::io::Error> for GameConfigError {
+    fn from(e: std::io::Error) -> Self {
+        GameConfigError::Io(e)
+    }
+}
+
+/// Loads `GameConfig` (picked by asset type, the file is plain `.ron`)
+#[derive(Default)]
+pub struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = GameConfigError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<GameConfig, GameConfigError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = std::str::from_utf8(&bytes).map_err(|e| GameConfigError::Parse(e.to_string()))?;
+        GameConfig::parse(text)
+    }
+}
+
+/// Handle keeping the config asset loaded
+#[derive(Resource, Debug, Default)]
+pub struct GameConfigHandle(pub Handle<GameConfig>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME_CONFIG_FILE: &str = include_str!("../../assets/data/game_config.ron");
+
+    #[test]
+    fn test_shipped_config_is_valid() {
+        let config = GameConfig::parse(GAME_CONFIG_FILE).unwrap();
+        assert_eq!(config.stage_limit(), 470.0);
+        assert_eq!(config.breaths, 3);
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let config = GameConfig::parse(GAME_CONFIG_FILE).unwrap();
+        let mut tuned = config.clone();
+        tuned.walk_speed = 320.0;
+        tuned.clear_color.0 = 0.2;
+        assert_eq!(config.changed_fields(&tuned), vec!["clear_color", "walk_speed"]);
+        assert!(config.changed_fields(&config).is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_rendering() {
+        let config = GameConfig::parse(GAME_CONFIG_FILE).unwrap();
+        let mut recolored = config.clone();
+        recolored.clear_color.0 = 0.2;
+        recolored.window_width = 1920.0;
+        assert_eq!(config.fingerprint(), recolored.fingerprint());
+
+        let mut tuned = config.clone();
+        tuned.walk_speed = 320.0;
+        assert_ne!(config.fingerprint(), tuned.fingerprint());
+    }
+
+    #[test]
+    fn test_rejects_spawn_outside_stage() {
+        let text = GAME_CONFIG_FILE.replace("spawn_offset: 300.0", "spawn_offset: 900.0");
+        assert!(matches!(GameConfig::parse(&text), Err(GameConfigError::Invalid(_))));
+    }
 }
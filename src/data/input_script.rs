@@ -0,0 +1,112 @@
+use std::path::Path;
+use bevy::prelude::*;
+use serde::Deserialize;
+use crate::systems::input::PlayerInput;
+
+/// Button in an input script step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ScriptButton {
+    Left,
+    Right,
+    Up,
+    Down,
+    Light,
+    Heavy,
+    Grab,
+    Block,
+    Step,
+    Backdash,
+}
+
+/// Hold a set of buttons for a number of frames
+///
+/// Attack buttons (`Light`, `Heavy`, `Grab`) are pressed once, on the first
+/// frame of the step, and stay held down for the rest of it (entering a
+/// stance); everything else is held for the whole step.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptStep {
+    pub frames: u32,
+    #[serde(default)]
+    pub hold: Vec<ScriptButton>,
+}
+
+/// Scripted inputs for the headless runner, loaded from a .ron file
+///
+/// ```ron
+/// (
+///     player_one: [(frames: 60, hold: [Right]), (frames: 20, hold: [Light])],
+///     player_two: [(frames: 120, hold: [Block])],
+/// )
+/// ```
+///
+/// A player whose script has run out stands still.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InputScript {
+    #[serde(default)]
+    pub player_one: Vec<ScriptStep>,
+    #[serde(default)]
+    pub player_two: Vec<ScriptStep>,
+}
+
+impl InputScript {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        ron::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// One input per frame for a player's steps
+    pub fn expand(steps: &[ScriptStep]) -> Vec<PlayerInput> {
+        let mut frames = Vec::new();
+        for step in steps {
+            for frame in 0..step.frames {
+                frames.push(step.input(frame == 0));
+            }
+        }
+        frames
+    }
+}
+
+impl ScriptStep {
+    fn input(&self, first_frame: bool) -> PlayerInput {
+        let held = |button: ScriptButton| self.hold.contains(&button);
+        let pressed = |button: ScriptButton| first_frame && held(button);
+        let axis = |negative: ScriptButton, positive: ScriptButton| held(positive) as i8 as f32 - held(negative) as i8 as f32;
+
+        PlayerInput {
+            movement: Vec2::new(
+                axis(ScriptButton::Left, ScriptButton::Right),
+                axis(ScriptButton::Down, ScriptButton::Up),
+            ),
+            light_attack: pressed(ScriptButton::Light),
+            heavy_attack: pressed(ScriptButton::Heavy),
+            grab: pressed(ScriptButton::Grab),
+            block: held(ScriptButton::Block),
+            step: held(ScriptButton::Step),
+            backdash: held(ScriptButton::Backdash),
+            light_held: held(ScriptButton::Light),
+            heavy_held: held(ScriptButton::Heavy),
+            grab_held: held(ScriptButton::Grab),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_presses_attacks_once() {
+        let script: InputScript = ron::from_str(
+            "(player_one: [(frames: 2, hold: [Right]), (frames: 3, hold: [Heavy, Block])])",
+        ).unwrap();
+        let inputs = InputScript::expand(&script.player_one);
+
+        assert_eq!(inputs.len(), 5);
+        assert_eq!(inputs[0].movement, Vec2::new(1.0, 0.0));
+        assert!(inputs[2].heavy_attack && inputs[2].block);
+        assert!(!inputs[3].heavy_attack && inputs[3].block);
+        assert!(inputs[3].heavy_held && !inputs[0].heavy_held);
+        assert!(script.player_two.is_empty());
+    }
+}
@@ -1,3 +1,6 @@
 // Data structures - Definitions for loading from .ron files
 
+pub mod character;
 pub mod game_config;
+pub mod input_script;
+pub mod replay;
@@ -0,0 +1,243 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::components::character::Player;
+use crate::systems::input::{CurrentInputs, PlayerInput};
+use crate::systems::snapshot::SimState;
+
+/// Directory replays are written to (relative to the working directory)
+pub const REPLAY_DIR: &str = "replays";
+
+/// File extension of replay files
+pub const REPLAY_EXTENSION: &str = "replay";
+
+/// Bumped whenever the layout below changes; older files are rejected
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// First bytes of every replay file
+const MAGIC: &[u8; 4] = b"FDRP";
+
+/// One fighter's setup at the start of the match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFighter {
+    pub player: Player,
+    /// Character name
+    pub character: String,
+    /// `Movelist::fingerprint` - playback warns if the movelist has changed
+    pub movelist_fingerprint: u64,
+}
+
+/// Everything needed to re-run a match apart from the inputs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    /// Crate version that recorded the match
+    pub game_version: String,
+    /// Unix time the match ended, in milliseconds
+    pub recorded_at: u64,
+    /// Simulation rate the inputs were recorded at
+    pub simulation_hz: u32,
+    pub fighters: Vec<ReplayFighter>,
+    /// `GameConfig::fingerprint` - playback warns if the config has changed
+    pub config_fingerprint: u64,
+    pub seed: u64,
+    /// Match result, for browsing replays without running them
+    pub winner: Option<Player>,
+    /// State before the first frame (includes `MatchState` settings)
+    pub initial_state: SimState,
+}
+
+/// A recorded match: initial conditions plus both players' input every frame
+///
+/// File layout (integers little-endian):
+///
+/// ```text
+/// "FDRP"  u32 format version  u32 header length  header (RON)
+/// input runs until end of file: u16 run length, u16 P1 input, u16 P2 input
+/// ```
+///
+/// Inputs are packed with `PlayerInput::to_bits` and run-length encoded, since
+/// most frames repeat the previous one.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    /// Inputs for each frame, starting at `header.initial_state.frame`
+    pub inputs: Vec<CurrentInputs>,
+}
+
+/// Why a replay couldn't be read or written
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    NotAReplay,
+    UnsupportedVersion(u32),
+    Header(String),
+    Truncated,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "{}", e),
+            ReplayError::NotAReplay => write!(f, "not a replay file"),
+            ReplayError::UnsupportedVersion(version) => write!(
+                f,
+                "replay format version {} is not supported (expected {})",
+                version, REPLAY_FORMAT_VERSION
+            ),
+            ReplayError::Header(e) => write!(f, "invalid replay header: {}", e),
+            ReplayError::Truncated => write!(f, "replay file is truncated"),
+        }
+    }
+}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(e: std::io::Error) -> Self {
+        ReplayError::Io(e)
+    }
+}
+
+impl Replay {
+    /// Number of recorded frames
+    pub fn frame_count(&self) -> u32 {
+        self.inputs.len() as u32
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, ReplayError> {
+        let header = ron::to_string(&self.header).map_err(|e| ReplayError::Header(e.to_string()))?;
+
+        let mut bytes = Vec::with_capacity(12 + header.len() + self.inputs.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&REPLAY_FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+
+        let packed = |inputs: &CurrentInputs| (inputs.player_one.to_bits(), inputs.player_two.to_bits());
+        let mut frames = self.inputs.iter().map(packed).peekable();
+        while let Some(frame) = frames.next() {
+            let mut run: u16 = 1;
+            while run < u16::MAX && frames.peek() == Some(&frame) {
+                frames.next();
+                run += 1;
+            }
+            bytes.extend_from_slice(&run.to_le_bytes());
+            bytes.extend_from_slice(&frame.0.to_le_bytes());
+            bytes.extend_from_slice(&frame.1.to_le_bytes());
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let read_u32 = |at: usize| -> Result<u32, ReplayError> {
+            let slice = bytes.get(at..at + 4).ok_or(ReplayError::Truncated)?;
+            Ok(u32::from_le_bytes(slice.try_into().expect("slice is 4 bytes")))
+        };
+
+        if bytes.get(..4) != Some(MAGIC.as_slice()) {
+            return Err(ReplayError::NotAReplay);
+        }
+        let version = read_u32(4)?;
+        if version != REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let header_len = read_u32(8)? as usize;
+        let header_bytes = bytes.get(12..12 + header_len).ok_or(ReplayError::Truncated)?;
+        let header_text = std::str::from_utf8(header_bytes).map_err(|e| ReplayError::Header(e.to_string()))?;
+        let header: ReplayHeader = ron::from_str(header_text).map_err(|e| ReplayError::Header(e.to_string()))?;
+
+        let runs = &bytes[12 + header_len..];
+        if !runs.len().is_multiple_of(6) {
+            return Err(ReplayError::Truncated);
+        }
+
+        let mut inputs = Vec::new();
+        for run in runs.chunks_exact(6) {
+            let length = u16::from_le_bytes([run[0], run[1]]);
+            let frame = CurrentInputs {
+                player_one: PlayerInput::from_bits(u16::from_le_bytes([run[2], run[3]])),
+                player_two: PlayerInput::from_bits(u16::from_le_bytes([run[4], run[5]])),
+            };
+            inputs.extend(std::iter::repeat_n(frame, length as usize));
+        }
+
+        Ok(Self { header, inputs })
+    }
+
+    /// Write to `REPLAY_DIR`, named after the recording time
+    pub fn save(&self) -> Result<PathBuf, ReplayError> {
+        std::fs::create_dir_all(REPLAY_DIR)?;
+        let path = Path::new(REPLAY_DIR).join(format!("match-{}.{}", self.header.recorded_at, REPLAY_EXTENSION));
+        std::fs::write(&path, self.encode()?)?;
+        Ok(path)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        Self::decode(&std::fs::read(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::breath::MatchState;
+
+    fn replay(inputs: Vec<CurrentInputs>) -> Replay {
+        Replay {
+            header: ReplayHeader {
+                game_version: "0.1.0".to_string(),
+                recorded_at: 1_700_000_000_000,
+                simulation_hz: 60,
+                fighters: vec![ReplayFighter {
+                    player: Player::One,
+                    character: "default".to_string(),
+                    movelist_fingerprint: 42,
+                }],
+                config_fingerprint: 99,
+                seed: 7,
+                winner: Some(Player::Two),
+                initial_state: SimState {
+                    frame: 0,
+                    match_state: MatchState::default(),
+                    winner: None,
+                    fighters: Vec::new(),
+                },
+            },
+            inputs,
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let walk = CurrentInputs {
+            player_one: PlayerInput::from_bits(0b10),
+            player_two: PlayerInput::default(),
+        };
+        let attack = CurrentInputs {
+            player_one: PlayerInput::from_bits(0b1_0000),
+            player_two: PlayerInput::from_bits(0b1000_0000),
+        };
+        let mut inputs = vec![CurrentInputs::default(); 300];
+        inputs.extend([walk; 40]);
+        inputs.push(attack);
+        inputs.extend([walk; 3]);
+
+        let original = replay(inputs);
+        let bytes = original.encode().unwrap();
+        let decoded = Replay::decode(&bytes).unwrap();
+
+        assert_eq!(decoded.inputs, original.inputs);
+        assert_eq!(decoded.header.seed, 7);
+        assert_eq!(decoded.header.winner, Some(Player::Two));
+        // Four runs of 6 bytes after the header
+        assert_eq!(bytes.len(), 12 + ron::to_string(&original.header).unwrap().len() + 4 * 6);
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let mut bytes = replay(Vec::new()).encode().unwrap();
+        bytes[4..8].copy_from_slice(&(REPLAY_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(Replay::decode(&bytes), Err(ReplayError::UnsupportedVersion(_))));
+        assert!(matches!(Replay::decode(b"nope"), Err(ReplayError::NotAReplay)));
+    }
+}
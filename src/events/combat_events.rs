@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use crate::components::state::AttackType;
+use crate::components::guard::ParryGrade;
+use crate::components::movelist::{MoveData, MoveId};
 
 /// Event fired when an attack hits a hurtbox
 #[derive(Event, Debug, Clone)]
@@ -8,28 +9,49 @@ pub struct HitEvent {
     pub attacker: Entity,
     /// Entity that was hit
     pub defender: Entity,
-    /// Amount of damage
-    pub damage: u8,
-    /// Type of attack
-    pub attack_type: AttackType,
+    /// Move that connected
+    pub move_id: MoveId,
+    /// Health damage on hit
+    pub damage: f32,
+    /// Health damage when blocked
+    pub chip_damage: f32,
+    /// Guard meter filled when blocked
+    pub guard_damage: f32,
+    /// Hitstop frames for both fighters, for hit, block or counter hit
+    pub hitstop: u32,
+    /// Hitstun or blockstun for the defender, from the connecting frame
+    pub stun_frames: u32,
+    /// Frames from the connecting frame until the attacker is back to Idle
+    pub attacker_frames_left: u32,
     /// Whether the hit was blocked
     pub was_blocked: bool,
     /// Properties of the attack
     pub unblockable: bool,
     /// Whether this was a counter hit (hit during startup)
     pub counter_hit: bool,
+    /// Whether an armored Heavy took the hit without being interrupted (Tenuki)
+    pub absorbed: bool,
+    /// Whether the defender parried the hit: no damage or hitstun
+    pub parried: bool,
 }
 
 impl HitEvent {
-    pub fn new(attacker: Entity, defender: Entity, damage: u8, attack_type: AttackType) -> Self {
+    pub fn new(attacker: Entity, defender: Entity, move_id: MoveId, move_data: &MoveData) -> Self {
         Self {
             attacker,
             defender,
-            damage,
-            attack_type,
+            move_id,
+            damage: move_data.damage,
+            chip_damage: move_data.chip_damage,
+            guard_damage: move_data.guard_damage,
+            hitstop: move_data.hitstop_on_hit,
+            stun_frames: 0,
+            attacker_frames_left: 0,
             was_blocked: false,
             unblockable: false,
             counter_hit: false,
+            absorbed: false,
+            parried: false,
         }
     }
 
@@ -47,6 +69,28 @@ impl HitEvent {
         self.counter_hit = true;
         self
     }
+
+    pub fn absorbed(mut self) -> Self {
+        self.absorbed = true;
+        self
+    }
+
+    pub fn parried(mut self) -> Self {
+        self.parried = true;
+        self
+    }
+
+    pub fn with_stun(mut self, stun_frames: u32, attacker_frames_left: u32) -> Self {
+        self.stun_frames = stun_frames;
+        self.attacker_frames_left = attacker_frames_left;
+        self
+    }
+
+    /// Frames the attacker recovers before a defender stunned for
+    /// `stun_frames` (negative: after)
+    pub fn frame_advantage(&self, stun_frames: u32) -> i32 {
+        stun_frames as i32 - self.attacker_frames_left as i32
+    }
 }
 
 /// Event fired when a parry successfully deflects an attack
@@ -56,6 +100,11 @@ pub struct ParryEvent {
     pub defender: Entity,
     /// Entity whose attack was parried
     pub attacker: Entity,
+    /// How well the parry was timed; only a Perfect parry earns a reward
+    pub grade: ParryGrade,
+    /// Made Perfect by Final Stand: full guard and initiative rewards,
+    /// whatever the character's parry data
+    pub final_stand: bool,
 }
 
 /// Event fired when guard meter fills and breaks
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use crate::components::state::CharacterState;
+use crate::systems::input::PlayerInput;
+
+/// Distance at which `Rushdown` stops walking in and starts attacking
+const RUSHDOWN_RANGE: f32 = 180.0;
+
+/// What a controller sees of the match on the frame it decides
+#[derive(Debug, Clone)]
+pub struct FighterView {
+    pub position: f32,
+    pub opponent_position: f32,
+    pub state: CharacterState,
+    /// Back to a wall (see `Corner`)
+    pub cornered: bool,
+    pub opponent_cornered: bool,
+}
+
+impl FighterView {
+    /// +1 if the opponent is to the right, -1 if to the left
+    fn toward_opponent(&self) -> f32 {
+        if self.opponent_position >= self.position { 1.0 } else { -1.0 }
+    }
+
+    fn can_act(&self) -> bool {
+        matches!(self.state, CharacterState::Idle | CharacterState::Walking)
+    }
+}
+
+/// Which controller to use for a player, as named on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControllerKind {
+    Idle,
+    Random,
+    Rushdown,
+    Script,
+}
+
+impl ControllerKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "idle" => Some(ControllerKind::Idle),
+            "random" => Some(ControllerKind::Random),
+            "rushdown" => Some(ControllerKind::Rushdown),
+            "script" => Some(ControllerKind::Script),
+            _ => None,
+        }
+    }
+}
+
+/// Produces one player's input each frame of a headless match
+#[derive(Debug, Clone)]
+pub enum Controller {
+    /// Never presses anything
+    Idle,
+    /// Picks a new random action every few frames (xorshift, seeded per match)
+    Random { rng: u32, current: PlayerInput, hold_frames: u32 },
+    /// Walks in and alternates light and heavy attacks in range, grabbing
+    /// instead of the heavy when the opponent is cornered
+    Rushdown { attacks: u32 },
+    /// Plays back a fixed input per frame, then stands still
+    Script { inputs: Vec<PlayerInput> },
+}
+
+impl Controller {
+    pub fn random(seed: u64) -> Self {
+        // xorshift must never start from zero
+        let rng = (seed ^ (seed >> 32)) as u32 | 1;
+        Controller::Random {
+            rng,
+            current: PlayerInput::default(),
+            hold_frames: 0,
+        }
+    }
+
+    /// Input for `frame` of the match
+    pub fn input(&mut self, frame: u32, view: &FighterView) -> PlayerInput {
+        match self {
+            Controller::Idle => PlayerInput::default(),
+            Controller::Random { rng, current, hold_frames } => {
+                if *hold_frames > 0 {
+                    *hold_frames -= 1;
+                    // Attacks are presses, only the first frame counts
+                    current.clear_presses();
+                    return *current;
+                }
+
+                let roll = next_random(rng);
+                *hold_frames = 4 + roll % 20;
+                *current = random_action(roll >> 8, view.toward_opponent(), view.cornered);
+                *current
+            }
+            Controller::Rushdown { attacks } => {
+                let toward = view.toward_opponent();
+                let distance = (view.opponent_position - view.position).abs();
+
+                if distance > RUSHDOWN_RANGE {
+                    return PlayerInput {
+                        movement: Vec2::new(toward, 0.0),
+                        ..default()
+                    };
+                }
+                if !view.can_act() {
+                    return PlayerInput::default();
+                }
+
+                *attacks += 1;
+                let finisher = *attacks % 3 == 0;
+                PlayerInput {
+                    light_attack: !finisher,
+                    heavy_attack: finisher && !view.opponent_cornered,
+                    grab: finisher && view.opponent_cornered,
+                    ..default()
+                }
+            }
+            Controller::Script { inputs } => inputs.get(frame as usize).copied().unwrap_or_default(),
+        }
+    }
+}
+
+fn next_random(state: &mut u32) -> u32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 17;
+    *state ^= *state << 5;
+    *state
+}
+
+/// Backing off is no use with the wall behind: a cornered fighter blocks instead
+fn random_action(roll: u32, toward: f32, cornered: bool) -> PlayerInput {
+    let forward = Vec2::new(toward, 0.0);
+    match roll % 8 {
+        0 => PlayerInput { movement: forward, ..default() },
+        1 if cornered => PlayerInput { block: true, ..default() },
+        1 => PlayerInput { movement: -forward, ..default() },
+        2 => PlayerInput { light_attack: true, ..default() },
+        3 => PlayerInput { heavy_attack: true, ..default() },
+        4 => PlayerInput { grab: true, ..default() },
+        5 => PlayerInput { block: true, ..default() },
+        6 => PlayerInput { movement: forward, step: true, ..default() },
+        _ => PlayerInput::default(),
+    }
+}
@@ -0,0 +1,236 @@
+// Headless - batch matches without a window, as fast as the CPU allows
+
+pub mod controller;
+
+use bevy::asset::{AssetPlugin, LoadState};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use crate::components::breath::MatchState;
+use crate::components::character::Player;
+use crate::data::character::Characters;
+use crate::data::game_config::{GameConfigHandle, GAME_CONFIG_PATH};
+use crate::data::input_script::InputScript;
+use crate::plugins::headless::HeadlessPlugin;
+use crate::systems::game_state::GameState;
+use crate::systems::headless::{HeadlessMatch, RoundResult};
+use crate::systems::simulation::{MatchSeed, SimFrame, SIMULATION_HZ};
+use controller::{Controller, ControllerKind};
+
+/// Match length cap when `--max-frames` isn't given (10 minutes of play)
+pub const DEFAULT_MAX_FRAMES: u32 = 60 * 60 * 10;
+
+/// Headless settings from the command line
+///
+/// ```text
+/// --headless [--matches <n>] [--p1 <controller>] [--p2 <controller>]
+///     [--script <file.ron>] [--seed <n>] [--max-frames <n>]
+/// ```
+///
+/// Controllers are `idle`, `random`, `rushdown` and `script`. Both players
+/// default to `script` when `--script` is given and `random` otherwise.
+#[derive(Debug, Clone)]
+pub struct HeadlessConfig {
+    pub matches: u32,
+    pub player_one: ControllerKind,
+    pub player_two: ControllerKind,
+    pub script: Option<InputScript>,
+    /// Seed of the first match; each following match adds one
+    pub seed: u64,
+    pub max_frames: u32,
+}
+
+impl HeadlessConfig {
+    /// Parse headless flags, `None` if `--headless` wasn't passed
+    pub fn from_args(args: &[String]) -> Option<Result<Self, String>> {
+        if !args.iter().any(|arg| arg == "--headless") {
+            return None;
+        }
+        Some(Self::parse(args))
+    }
+
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| -> Option<&str> {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|index| args.get(index + 1))
+                .map(String::as_str)
+        };
+        let number = |flag: &str, default: u64| -> Result<u64, String> {
+            match value(flag) {
+                Some(text) => text.parse().map_err(|_| format!("{} expects a number, got '{}'", flag, text)),
+                None => Ok(default),
+            }
+        };
+
+        let script = value("--script").map(InputScript::load).transpose()?;
+        let default_kind = if script.is_some() { ControllerKind::Script } else { ControllerKind::Random };
+        let controller = |flag: &str| -> Result<ControllerKind, String> {
+            match value(flag) {
+                Some(name) => ControllerKind::parse(name)
+                    .ok_or_else(|| format!("{} must be idle, random, rushdown or script, got '{}'", flag, name)),
+                None => Ok(default_kind),
+            }
+        };
+        let player_one = controller("--p1")?;
+        let player_two = controller("--p2")?;
+
+        if script.is_none() && (player_one == ControllerKind::Script || player_two == ControllerKind::Script) {
+            return Err("the script controller needs --script <file.ron>".to_string());
+        }
+
+        Ok(Self {
+            matches: number("--matches", 1)? as u32,
+            player_one,
+            player_two,
+            script,
+            seed: number("--seed", 0)?,
+            max_frames: number("--max-frames", DEFAULT_MAX_FRAMES as u64)? as u32,
+        })
+    }
+
+    fn controller(&self, kind: ControllerKind, player: Player, seed: u64) -> Controller {
+        match kind {
+            ControllerKind::Idle => Controller::Idle,
+            ControllerKind::Random => Controller::random(seed.wrapping_mul(2).wrapping_add(player as u64)),
+            ControllerKind::Rushdown => Controller::Rushdown { attacks: 0 },
+            ControllerKind::Script => {
+                let script = self.script.as_ref().expect("checked when parsing");
+                let steps = match player {
+                    Player::One => &script.player_one,
+                    Player::Two => &script.player_two,
+                };
+                Controller::Script { inputs: InputScript::expand(steps) }
+            }
+        }
+    }
+}
+
+/// Outcome of one headless match
+#[derive(Debug, Clone)]
+pub struct MatchResult {
+    pub seed: u64,
+    /// `None` if the match hit `max_frames` first
+    pub winner: Option<Player>,
+    pub frames: u32,
+    pub rounds: Vec<RoundResult>,
+}
+
+/// Run every match back to back, printing each result and a summary
+///
+/// Fails if the game config or character files don't load.
+pub fn run(config: &HeadlessConfig) -> Result<Vec<MatchResult>, String> {
+    let timestep = Time::<Fixed>::from_hz(SIMULATION_HZ).timestep();
+
+    let mut app = App::new();
+    // Data files are read once: a batch must not change rules halfway through
+    let assets = AssetPlugin {
+        watch_for_changes_override: Some(false),
+        ..default()
+    };
+    app.add_plugins((MinimalPlugins, assets, StatesPlugin, HeadlessPlugin))
+        // Every update advances the clock by exactly one simulation tick
+        .insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+    app.finish();
+    app.cleanup();
+
+    // Game config and character files load in the background
+    while *app.world().resource::<State<GameState>>() == GameState::Loading {
+        if app.should_exit().is_some() {
+            return Err(load_failure(app.world()));
+        }
+        app.update();
+    }
+
+    let mut results = Vec::new();
+    for index in 0..config.matches {
+        let seed = config.seed.wrapping_add(index as u64);
+        let result = run_match(&mut app, config, seed);
+        print_result(index + 1, &result);
+        results.push(result);
+    }
+
+    let wins = |player: Player| results.iter().filter(|result| result.winner == Some(player)).count();
+    println!(
+        "Summary: {} matches - Player One {}, Player Two {}, unfinished {}",
+        results.len(),
+        wins(Player::One),
+        wins(Player::Two),
+        results.iter().filter(|result| result.winner.is_none()).count()
+    );
+    Ok(results)
+}
+
+/// Why a data file failed to load (no log output under `MinimalPlugins`)
+fn load_failure(world: &World) -> String {
+    let asset_server = world.resource::<AssetServer>();
+    if let LoadState::Failed(e) = asset_server.load_state(&world.resource::<GameConfigHandle>().0) {
+        return format!("{} failed to load: {}", GAME_CONFIG_PATH, e);
+    }
+    world
+        .resource::<Characters>()
+        .handles
+        .iter()
+        .find_map(|(name, handle)| match asset_server.load_state(handle) {
+            LoadState::Failed(e) => Some(format!("character '{}' failed to load: {}", name, e)),
+            _ => None,
+        })
+        .unwrap_or_else(|| "game data failed to load".to_string())
+}
+
+fn run_match(app: &mut App, config: &HeadlessConfig, seed: u64) -> MatchResult {
+    app.insert_resource(MatchSeed(seed));
+    app.insert_resource(HeadlessMatch {
+        player_one: config.controller(config.player_one, Player::One, seed),
+        player_two: config.controller(config.player_two, Player::Two, seed),
+        rounds: Vec::new(),
+        cornered_frames: (0, 0),
+    });
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::InGame);
+
+    loop {
+        app.update();
+        let world = app.world();
+        let frames = world.resource::<SimFrame>().0;
+        let match_state = world.resource::<MatchState>();
+        if match_state.match_over || frames >= config.max_frames {
+            break;
+        }
+    }
+
+    let world = app.world_mut();
+    let winner = world
+        .resource::<MatchState>()
+        .winner
+        .and_then(|entity| world.get::<Player>(entity).copied());
+    let result = MatchResult {
+        seed,
+        winner,
+        frames: world.resource::<SimFrame>().0,
+        rounds: std::mem::take(&mut world.resource_mut::<HeadlessMatch>().rounds),
+    };
+
+    // Leave the match so the next one starts from freshly spawned fighters
+    world.resource_mut::<NextState<GameState>>().set(GameState::CharacterSelect);
+    app.update();
+
+    result
+}
+
+fn print_result(number: u32, result: &MatchResult) {
+    match result.winner {
+        Some(winner) => println!("Match {} (seed {}): {:?} wins after {} frames", number, result.seed, winner, result.frames),
+        None => println!("Match {} (seed {}): unfinished after {} frames", number, result.seed, result.frames),
+    }
+    for (round, outcome) in result.rounds.iter().enumerate() {
+        println!(
+            "  Round {}: {:?} by {:?} at frame {} (cornered: One {}f, Two {}f)",
+            round + 1,
+            outcome.winner,
+            outcome.reason,
+            outcome.frame,
+            outcome.cornered_frames.0,
+            outcome.cornered_frames.1
+        );
+    }
+}
@@ -4,12 +4,64 @@ use bevy_framepace::{FramepacePlugin, FramepaceSettings, Limiter};
 mod components;
 mod data;
 mod events;
+mod headless;
+mod netplay;
 mod plugins;
 mod resources;
 mod systems;
 
 fn main() {
-    App::new()
+    let args: Vec<String> = std::env::args().collect();
+
+    // Batch matches without a window: run them, print the results and exit
+    match headless::HeadlessConfig::from_args(&args) {
+        Some(Ok(config)) => {
+            if let Err(message) = headless::run(&config) {
+                eprintln!("Headless: {}", message);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Err(message)) => {
+            eprintln!("Headless: {}", message);
+            std::process::exit(2);
+        }
+        None => {}
+    }
+
+    let netplay_config = match netplay::NetplayConfig::from_args(&args) {
+        Some(Ok(config)) => Some(config),
+        Some(Err(message)) => {
+            eprintln!("Netplay: {}", message);
+            std::process::exit(2);
+        }
+        None => None,
+    };
+
+    // Replay playback: `--replay <file>` watches a recorded match instead of playing
+    let replay = match args.iter().position(|arg| arg == "--replay") {
+        Some(index) => {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Replay: --replay expects a replay file");
+                std::process::exit(2);
+            };
+            match data::replay::Replay::load(path) {
+                Ok(replay) => Some(replay),
+                Err(e) => {
+                    eprintln!("Replay: failed to load {}: {}", path, e);
+                    std::process::exit(2);
+                }
+            }
+        }
+        None => None,
+    };
+    if replay.is_some() && netplay_config.is_some() {
+        eprintln!("Replay: --replay can't be combined with --netplay");
+        std::process::exit(2);
+    }
+
+    let mut app = App::new();
+    app
         // Window and rendering setup
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -26,28 +78,58 @@ fn main() {
         .add_plugins(plugins::core_game::CoreGamePlugin)
         // Setup
         .add_systems(Startup, setup)
-        .run();
-}
+        .add_systems(OnExit(systems::game_state::GameState::Loading), apply_game_config);
+
+    // Determinism check: re-simulate every window of play and report the first divergence
+    if args.iter().any(|arg| arg == "--sync-test") {
+        app.init_resource::<systems::sync_test::SyncTest>();
+    }
 
-/// Initial scene setup - only persistent elements (camera, stage)
-fn setup(mut commands: Commands, mut framepace: ResMut<FramepaceSettings>) {
-    // Lock to 60 FPS
-    framepace.limiter = Limiter::from_framerate(60.0);
+    // Online play: rollback netcode drives the simulation instead of the local keyboard
+    if let Some(config) = netplay_config {
+        app.add_plugins(plugins::netplay::NetplayPlugin { config });
+    }
 
+    if let Some(replay) = replay {
+        app.add_plugins(plugins::replay::ReplayPlaybackPlugin { replay });
+    }
+
+    app.run();
+}
+
+/// Initial scene setup - only persistent elements (camera)
+fn setup(mut commands: Commands) {
     // Camera
     commands.spawn(Camera2dBundle::default());
 
+    info!("Fudoshin initialized");
+}
+
+/// Apply window, frame rate and stage settings from the loaded `GameConfig`
+fn apply_game_config(
+    mut commands: Commands,
+    config: Res<data::game_config::GameConfig>,
+    mut windows: Query<&mut Window>,
+    mut framepace: ResMut<FramepaceSettings>,
+) {
+    // Frame limit (the simulation itself always ticks at 60 Hz)
+    framepace.limiter = Limiter::from_framerate(config.target_fps as f64);
+
+    for mut window in windows.iter_mut() {
+        window.resolution.set(config.window_width, config.window_height);
+    }
+    commands.insert_resource(ClearColor(config.clear_color()));
+
     // Stage boundaries (visual reference for now)
     commands.spawn(SpriteBundle {
         sprite: Sprite {
             color: Color::srgb(0.2, 0.2, 0.25),
-            custom_size: Some(Vec2::new(1000.0, 600.0)),
+            custom_size: Some(Vec2::new(config.stage_width, config.stage_height)),
             ..default()
         },
         transform: Transform::from_xyz(0.0, 0.0, -1.0),
         ..default()
     });
 
-    info!("Fudoshin initialized");
     info!("Game starts in Character Select - press J or Numpad1 to ready up");
 }
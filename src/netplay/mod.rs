@@ -0,0 +1,94 @@
+// Netplay - peer-to-peer rollback over UDP
+
+pub mod protocol;
+pub mod session;
+pub mod transport;
+
+use std::net::{SocketAddr, ToSocketAddrs};
+use bevy::prelude::*;
+use crate::components::character::Player;
+use session::RollbackSession;
+use transport::{ShimSettings, UdpTransport};
+
+/// Frames of input delay when `--input-delay` isn't given
+pub const DEFAULT_INPUT_DELAY: u32 = 2;
+
+/// Netplay settings from the command line
+///
+/// ```text
+/// --netplay --player <1|2> --port <local port> --peer <host:port>
+///     [--input-delay <frames>] [--sim-latency <ms>] [--sim-jitter <ms>] [--sim-loss <percent>]
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct NetplayConfig {
+    pub local_player: Player,
+    pub local_port: u16,
+    pub peer: SocketAddr,
+    pub input_delay: u32,
+    pub shim: ShimSettings,
+}
+
+impl NetplayConfig {
+    /// Parse netplay flags, `None` if `--netplay` wasn't passed
+    pub fn from_args(args: &[String]) -> Option<Result<Self, String>> {
+        if !args.iter().any(|arg| arg == "--netplay") {
+            return None;
+        }
+        Some(Self::parse(args))
+    }
+
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| -> Option<&str> {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|index| args.get(index + 1))
+                .map(String::as_str)
+        };
+        let number = |flag: &str, default: u32| -> Result<u32, String> {
+            match value(flag) {
+                Some(text) => text.parse().map_err(|_| format!("{} expects a number, got '{}'", flag, text)),
+                None => Ok(default),
+            }
+        };
+
+        let local_player = match value("--player") {
+            Some("1") => Player::One,
+            Some("2") => Player::Two,
+            _ => return Err("--player must be 1 or 2".to_string()),
+        };
+
+        let local_port = value("--port")
+            .ok_or("--port is required")?
+            .parse()
+            .map_err(|_| "--port must be a port number".to_string())?;
+
+        let peer_text = value("--peer").ok_or("--peer is required (e.g. 127.0.0.1:7001)")?;
+        // The socket is bound to IPv4, so prefer an IPv4 address for the peer
+        let peer = peer_text
+            .to_socket_addrs()
+            .map_err(|e| format!("Invalid --peer '{}': {}", peer_text, e))?
+            .find(SocketAddr::is_ipv4)
+            .ok_or_else(|| format!("--peer '{}' has no IPv4 address", peer_text))?;
+
+        let shim = ShimSettings {
+            latency_ms: number("--sim-latency", 0)?,
+            jitter_ms: number("--sim-jitter", 0)?,
+            loss_percent: number("--sim-loss", 0)?.min(100),
+        };
+
+        Ok(Self {
+            local_player,
+            local_port,
+            peer,
+            input_delay: number("--input-delay", DEFAULT_INPUT_DELAY)?,
+            shim,
+        })
+    }
+}
+
+/// Live netplay connection: socket plus the current match's rollback session
+#[derive(Resource)]
+pub struct Netplay {
+    pub transport: UdpTransport,
+    pub session: RollbackSession,
+}
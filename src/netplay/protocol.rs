@@ -0,0 +1,154 @@
+/// First byte of every packet, rejects stray traffic on the port
+const MAGIC: u8 = 0xF5;
+
+const KIND_HELLO: u8 = 0;
+const KIND_INPUTS: u8 = 1;
+const KIND_CHECKSUM: u8 = 2;
+
+/// Most inputs carried by one packet (about one second of unacknowledged frames)
+pub const MAX_INPUTS_PER_PACKET: usize = 64;
+
+/// Fingerprints of the data both peers must simulate with
+///
+/// Peers with different movelists or config would desync from the first
+/// frame, so the handshake refuses them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DataFingerprints {
+    /// `Movelist::fingerprint` of the character both peers play
+    pub movelist: u64,
+    /// `GameConfig::fingerprint`
+    pub config: u64,
+}
+
+/// Messages exchanged between the two peers
+///
+/// Inputs are sent redundantly: every packet carries all local inputs the peer
+/// has not acknowledged yet, so a lost packet is repaired by the next one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetMessage {
+    /// Sent until the peer answers, before any inputs are exchanged
+    Hello(DataFingerprints),
+    /// A run of consecutive inputs starting at `start_frame`
+    Inputs {
+        /// Number of the receiver's frames the sender has received so far
+        ack: u32,
+        /// Frame of the first input in `inputs`
+        start_frame: u32,
+        /// Inputs packed with `PlayerInput::to_bits`
+        inputs: Vec<u16>,
+    },
+    /// Checksum of the sender's state after `frame` confirmed frames
+    Checksum { frame: u32, value: u64 },
+}
+
+impl NetMessage {
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            NetMessage::Hello(fingerprints) => {
+                let mut bytes = Vec::with_capacity(18);
+                bytes.push(MAGIC);
+                bytes.push(KIND_HELLO);
+                bytes.extend_from_slice(&fingerprints.movelist.to_le_bytes());
+                bytes.extend_from_slice(&fingerprints.config.to_le_bytes());
+                bytes
+            }
+            NetMessage::Inputs { ack, start_frame, inputs } => {
+                let count = inputs.len().min(MAX_INPUTS_PER_PACKET);
+                let mut bytes = Vec::with_capacity(11 + count * 2);
+                bytes.push(MAGIC);
+                bytes.push(KIND_INPUTS);
+                bytes.extend_from_slice(&ack.to_le_bytes());
+                bytes.extend_from_slice(&start_frame.to_le_bytes());
+                bytes.push(count as u8);
+                for input in &inputs[..count] {
+                    bytes.extend_from_slice(&input.to_le_bytes());
+                }
+                bytes
+            }
+            NetMessage::Checksum { frame, value } => {
+                let mut bytes = Vec::with_capacity(14);
+                bytes.push(MAGIC);
+                bytes.push(KIND_CHECKSUM);
+                bytes.extend_from_slice(&frame.to_le_bytes());
+                bytes.extend_from_slice(&value.to_le_bytes());
+                bytes
+            }
+        }
+    }
+
+    /// Parse a packet, `None` if it is malformed or not ours
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 2 || bytes[0] != MAGIC {
+            return None;
+        }
+
+        match bytes[1] {
+            KIND_HELLO => {
+                let movelist = u64::from_le_bytes(bytes.get(2..10)?.try_into().ok()?);
+                let config = u64::from_le_bytes(bytes.get(10..18)?.try_into().ok()?);
+                Some(NetMessage::Hello(DataFingerprints { movelist, config }))
+            }
+            KIND_INPUTS => {
+                let ack = u32::from_le_bytes(bytes.get(2..6)?.try_into().ok()?);
+                let start_frame = u32::from_le_bytes(bytes.get(6..10)?.try_into().ok()?);
+                let count = *bytes.get(10)? as usize;
+                let payload = bytes.get(11..11 + count * 2)?;
+                let inputs = payload
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                Some(NetMessage::Inputs { ack, start_frame, inputs })
+            }
+            KIND_CHECKSUM => {
+                let frame = u32::from_le_bytes(bytes.get(2..6)?.try_into().ok()?);
+                let value = u64::from_le_bytes(bytes.get(6..14)?.try_into().ok()?);
+                Some(NetMessage::Checksum { frame, value })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::input::PlayerInput;
+    use bevy::math::Vec2;
+
+    #[test]
+    fn test_inputs_round_trip() {
+        let message = NetMessage::Inputs {
+            ack: 120,
+            start_frame: 118,
+            inputs: vec![0, 0b11_0101_0110, u16::MAX],
+        };
+        assert_eq!(NetMessage::decode(&message.encode()), Some(message));
+        let hello = NetMessage::Hello(DataFingerprints { movelist: 42, config: 0xC0FF_EE00 });
+        assert_eq!(NetMessage::decode(&hello.encode()), Some(hello));
+
+        let checksum = NetMessage::Checksum { frame: 600, value: 0xDEAD_BEEF_0BAD_F00D };
+        assert_eq!(NetMessage::decode(&checksum.encode()), Some(checksum));
+    }
+
+    #[test]
+    fn test_rejects_truncated_packet() {
+        let mut bytes = NetMessage::Inputs { ack: 1, start_frame: 0, inputs: vec![3, 4] }.encode();
+        bytes.pop();
+        assert_eq!(NetMessage::decode(&bytes), None);
+        assert_eq!(NetMessage::decode(&[0x00, KIND_HELLO]), None);
+        assert_eq!(NetMessage::decode(&[MAGIC, KIND_HELLO]), None);
+    }
+
+    #[test]
+    fn test_player_input_bits_round_trip() {
+        let input = PlayerInput {
+            movement: Vec2::new(-1.0, 1.0),
+            heavy_attack: true,
+            block: true,
+            step: true,
+            heavy_held: true,
+            ..Default::default()
+        };
+        assert_eq!(PlayerInput::from_bits(input.to_bits()), input);
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::VecDeque;
+use crate::components::character::Player;
+use crate::netplay::protocol::{DataFingerprints, NetMessage, MAX_INPUTS_PER_PACKET};
+use crate::systems::input::PlayerInput;
+use crate::systems::snapshot::SimState;
+
+/// How far the local simulation may run ahead of confirmed remote input
+///
+/// Beyond this the session stalls instead of predicting further, which bounds
+/// both the rollback cost and the number of snapshots kept.
+pub const MAX_PREDICTION_FRAMES: u32 = 8;
+
+/// Snapshots kept for rolling back (prediction window plus margin)
+const SNAPSHOT_HISTORY: usize = MAX_PREDICTION_FRAMES as usize + 2;
+
+/// Confirmed frames between checksum exchanges (once a second)
+pub const CHECKSUM_INTERVAL: u32 = 60;
+
+/// Checksums kept per side while waiting for the other side's
+const CHECKSUM_HISTORY: usize = 16;
+
+/// GGPO-style rollback bookkeeping for one peer
+///
+/// Owns the input history of both players and the snapshot ring buffer, but
+/// never touches the ECS world itself: the netplay driver asks it which
+/// inputs to simulate, which frame to roll back to, and when to stall.
+///
+/// A peer whose Hello carries other data fingerprints is refused: the session
+/// never connects and ignores everything it sends.
+pub struct RollbackSession {
+    local_player: Player,
+    /// Our data, sent in Hello and compared against the peer's
+    fingerprints: DataFingerprints,
+    /// The peer's data when it differs from ours
+    peer_mismatch: Option<DataFingerprints>,
+    mismatch_reported: bool,
+    /// Next frame to simulate
+    current_frame: u32,
+    /// Local inputs by frame (the first `input_delay` frames are neutral)
+    local_inputs: Vec<PlayerInput>,
+    /// Confirmed remote inputs by frame
+    remote_inputs: Vec<PlayerInput>,
+    /// Remote input actually simulated for each frame (confirmed or predicted)
+    simulated_remote: Vec<PlayerInput>,
+    /// Number of our frames the peer has confirmed receiving
+    peer_ack: u32,
+    /// Earliest frame simulated with a wrong prediction
+    rollback_to: Option<u32>,
+    /// States saved right before simulating each recent frame
+    snapshots: VecDeque<SimState>,
+    connected: bool,
+    /// Our checksums of confirmed frames, oldest first
+    local_checksums: VecDeque<(u32, u64)>,
+    /// Peer checksums not yet matched against ours
+    remote_checksums: VecDeque<(u32, u64)>,
+    /// First frame where the checksums disagreed
+    desync_frame: Option<u32>,
+    desync_reported: bool,
+}
+
+impl RollbackSession {
+    /// Input delay is just `input_delay` neutral frames queued ahead of the
+    /// first local input
+    pub fn new(local_player: Player, input_delay: u32, fingerprints: DataFingerprints) -> Self {
+        Self {
+            local_player,
+            fingerprints,
+            peer_mismatch: None,
+            mismatch_reported: false,
+            current_frame: 0,
+            local_inputs: vec![PlayerInput::default(); input_delay as usize],
+            remote_inputs: Vec::new(),
+            simulated_remote: Vec::new(),
+            peer_ack: 0,
+            rollback_to: None,
+            snapshots: VecDeque::with_capacity(SNAPSHOT_HISTORY),
+            connected: false,
+            local_checksums: VecDeque::with_capacity(CHECKSUM_HISTORY),
+            remote_checksums: VecDeque::with_capacity(CHECKSUM_HISTORY),
+            desync_frame: None,
+            desync_reported: false,
+        }
+    }
+
+    pub fn fingerprints(&self) -> DataFingerprints {
+        self.fingerprints
+    }
+
+    pub fn current_frame(&self) -> u32 {
+        self.current_frame
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Number of remote frames received so far
+    pub fn confirmed_frames(&self) -> u32 {
+        self.remote_inputs.len() as u32
+    }
+
+    /// True when every simulated frame used confirmed remote input
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_frames() >= self.current_frame
+    }
+
+    /// Queue this tick's local input (it applies `input_delay` frames from now)
+    pub fn add_local_input(&mut self, input: PlayerInput) {
+        self.local_inputs.push(input);
+    }
+
+    /// True when the next frame can't be simulated yet
+    ///
+    /// Either the peer hasn't answered, or we'd predict too far ahead of it.
+    /// No local input is sampled while stalled, so the delay stays constant.
+    pub fn should_stall(&self) -> bool {
+        !self.connected || self.current_frame >= self.confirmed_frames() + MAX_PREDICTION_FRAMES
+    }
+
+    /// Apply a message from the peer
+    pub fn handle_message(&mut self, message: NetMessage) {
+        if self.peer_mismatch.is_some() {
+            return;
+        }
+        if let NetMessage::Hello(theirs) = message {
+            if theirs != self.fingerprints {
+                self.peer_mismatch = Some(theirs);
+                return;
+            }
+        }
+        self.connected = true;
+
+        let (ack, start_frame, inputs) = match message {
+            NetMessage::Hello(_) => return,
+            NetMessage::Checksum { frame, value } => {
+                if !self.remote_checksums.contains(&(frame, value)) {
+                    push_bounded(&mut self.remote_checksums, (frame, value));
+                }
+                self.compare_checksums();
+                return;
+            }
+            NetMessage::Inputs { ack, start_frame, inputs } => (ack, start_frame, inputs),
+        };
+
+        self.peer_ack = self.peer_ack.max(ack);
+
+        for (offset, bits) in inputs.into_iter().enumerate() {
+            let frame = start_frame as usize + offset;
+            // Only accept the next expected frame; duplicates and gaps are
+            // covered by redundant resends
+            if frame != self.remote_inputs.len() {
+                continue;
+            }
+
+            let input = PlayerInput::from_bits(bits);
+            self.remote_inputs.push(input);
+
+            if let Some(simulated) = self.simulated_remote.get(frame) {
+                if *simulated != input {
+                    let frame = frame as u32;
+                    self.rollback_to = Some(self.rollback_to.map_or(frame, |f| f.min(frame)));
+                }
+            }
+        }
+    }
+
+    /// Message carrying every local input the peer hasn't acknowledged
+    pub fn outgoing_message(&self) -> NetMessage {
+        if !self.connected {
+            return NetMessage::Hello(self.fingerprints);
+        }
+
+        let start = (self.peer_ack as usize).min(self.local_inputs.len());
+        let end = self.local_inputs.len().min(start + MAX_INPUTS_PER_PACKET);
+        NetMessage::Inputs {
+            ack: self.confirmed_frames(),
+            start_frame: start as u32,
+            inputs: self.local_inputs[start..end].iter().copied().map(PlayerInput::to_bits).collect(),
+        }
+    }
+
+    /// Remote input for a frame: confirmed if received, otherwise predicted
+    ///
+    /// Prediction repeats the last confirmed input with its presses cleared,
+    /// since held directions and block usually continue but a button press
+    /// is a one-frame event.
+    fn remote_input(&self, frame: u32) -> PlayerInput {
+        if let Some(input) = self.remote_inputs.get(frame as usize) {
+            return *input;
+        }
+
+        let mut predicted = self.remote_inputs.last().copied().unwrap_or_default();
+        predicted.clear_presses();
+        predicted
+    }
+
+    /// Inputs to simulate `frame` with, ordered (Player One, Player Two)
+    ///
+    /// Records the remote input used so a later correction can be detected.
+    pub fn inputs_for_frame(&mut self, frame: u32) -> (PlayerInput, PlayerInput) {
+        let local = self.local_inputs.get(frame as usize).copied().unwrap_or_default();
+        let remote = self.remote_input(frame);
+
+        let index = frame as usize;
+        if index < self.simulated_remote.len() {
+            self.simulated_remote[index] = remote;
+        } else {
+            self.simulated_remote.push(remote);
+        }
+
+        match self.local_player {
+            Player::One => (local, remote),
+            Player::Two => (remote, local),
+        }
+    }
+
+    /// Store the state from right before simulating its frame
+    pub fn save_snapshot(&mut self, state: SimState) {
+        self.snapshots.retain(|saved| saved.frame < state.frame);
+        self.snapshots.push_back(state);
+        while self.snapshots.len() > SNAPSHOT_HISTORY {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Take the pending rollback, with the snapshot to restore
+    ///
+    /// Returns `None` when no prediction was wrong. A rollback further back than
+    /// the snapshot history is impossible because of `MAX_PREDICTION_FRAMES`.
+    pub fn take_rollback(&mut self) -> Option<SimState> {
+        let frame = self.rollback_to.take()?;
+        self.snapshots.iter().find(|saved| saved.frame == frame).cloned()
+    }
+
+    /// Mark the current frame as simulated
+    pub fn advance_frame(&mut self) {
+        self.current_frame += 1;
+    }
+
+    /// Checksum snapshots that can no longer be rolled back
+    ///
+    /// The snapshot saved before frame `k` holds the state after `k` frames; once
+    /// the peer's inputs for all of them have arrived it is final on both sides.
+    /// Call after any pending rollback has been resimulated.
+    pub fn checksum_confirmed_frames(&mut self) {
+        let confirmed = self.confirmed_frames().min(self.current_frame);
+        let last_checksummed = self.local_checksums.back().map_or(0, |(frame, _)| *frame);
+
+        let due: Vec<(u32, u64)> = self
+            .snapshots
+            .iter()
+            .filter(|saved| saved.frame > last_checksummed && saved.frame <= confirmed)
+            .filter(|saved| saved.frame % CHECKSUM_INTERVAL == 0)
+            .map(|saved| (saved.frame, saved.checksum()))
+            .collect();
+
+        for checksum in due {
+            push_bounded(&mut self.local_checksums, checksum);
+        }
+        self.compare_checksums();
+    }
+
+    /// Our latest confirmed checksum, resent every tick until superseded
+    pub fn outgoing_checksum(&self) -> Option<NetMessage> {
+        self.local_checksums
+            .back()
+            .map(|&(frame, value)| NetMessage::Checksum { frame, value })
+    }
+
+    /// The peer's data fingerprints, returned once when they differ from ours
+    pub fn take_peer_mismatch(&mut self) -> Option<DataFingerprints> {
+        if self.mismatch_reported {
+            return None;
+        }
+        self.mismatch_reported = self.peer_mismatch.is_some();
+        self.peer_mismatch
+    }
+
+    /// First desynced frame, returned once when it is detected
+    pub fn take_desync(&mut self) -> Option<u32> {
+        if self.desync_reported {
+            return None;
+        }
+        self.desync_reported = self.desync_frame.is_some();
+        self.desync_frame
+    }
+
+    fn compare_checksums(&mut self) {
+        let local_checksums = &self.local_checksums;
+        let desync_frame = &mut self.desync_frame;
+
+        self.remote_checksums.retain(|(frame, remote)| {
+            let Some((_, local)) = local_checksums.iter().find(|(f, _)| f == frame) else {
+                return true;
+            };
+            if local != remote && desync_frame.is_none_or(|first| *frame < first) {
+                *desync_frame = Some(*frame);
+            }
+            false
+        });
+    }
+}
+
+fn push_bounded(history: &mut VecDeque<(u32, u64)>, entry: (u32, u64)) {
+    history.push_back(entry);
+    if history.len() > CHECKSUM_HISTORY {
+        history.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::breath::MatchState;
+
+    fn state_at(frame: u32) -> SimState {
+        SimState {
+            frame,
+            match_state: MatchState::default(),
+            winner: None,
+            fighters: Vec::new(),
+        }
+    }
+
+    fn inputs(frames: std::ops::Range<u32>, bits: u16) -> NetMessage {
+        NetMessage::Inputs {
+            ack: 0,
+            start_frame: frames.start,
+            inputs: frames.map(|_| bits).collect(),
+        }
+    }
+
+    /// Simulate one frame the way the netplay driver does
+    fn step(session: &mut RollbackSession) -> (PlayerInput, PlayerInput) {
+        session.add_local_input(PlayerInput::default());
+        let frame = session.current_frame();
+        session.save_snapshot(state_at(frame));
+        let used = session.inputs_for_frame(frame);
+        session.advance_frame();
+        used
+    }
+
+    #[test]
+    fn test_stalls_until_connected_and_within_prediction() {
+        let mut session = RollbackSession::new(Player::One, 0, DataFingerprints::default());
+        assert!(session.should_stall());
+
+        session.handle_message(NetMessage::Hello(DataFingerprints::default()));
+        for _ in 0..MAX_PREDICTION_FRAMES {
+            assert!(!session.should_stall());
+            step(&mut session);
+        }
+        assert!(session.should_stall());
+
+        session.handle_message(inputs(0..1, 0));
+        assert!(!session.should_stall());
+    }
+
+    #[test]
+    fn test_refuses_peer_with_other_data() {
+        let ours = DataFingerprints { movelist: 1, config: 2 };
+        let theirs = DataFingerprints { movelist: 1, config: 3 };
+        let mut session = RollbackSession::new(Player::One, 0, ours);
+
+        session.handle_message(NetMessage::Hello(theirs));
+        session.handle_message(inputs(0..1, 0));
+        assert!(!session.is_connected());
+        assert_eq!(session.confirmed_frames(), 0);
+        assert_eq!(session.outgoing_message(), NetMessage::Hello(ours));
+        assert_eq!(session.take_peer_mismatch(), Some(theirs));
+        assert_eq!(session.take_peer_mismatch(), None);
+
+        let mut session = RollbackSession::new(Player::One, 0, ours);
+        session.handle_message(NetMessage::Hello(ours));
+        assert!(session.is_connected());
+        assert_eq!(session.take_peer_mismatch(), None);
+    }
+
+    #[test]
+    fn test_misprediction_requests_rollback() {
+        let mut session = RollbackSession::new(Player::Two, 0, DataFingerprints::default());
+        session.handle_message(NetMessage::Hello(DataFingerprints::default()));
+
+        let hold_right = PlayerInput::from_bits(0b10);
+        session.handle_message(inputs(0..1, hold_right.to_bits()));
+
+        // Frames 1-3 are predicted as "still holding right"
+        for _ in 0..4 {
+            let (remote, _) = step(&mut session);
+            assert_eq!(remote, hold_right);
+        }
+        assert!(session.take_rollback().is_none());
+
+        // Frame 1 confirmed as predicted, frame 2 released right
+        session.handle_message(inputs(1..2, hold_right.to_bits()));
+        assert!(session.take_rollback().is_none());
+        session.handle_message(inputs(2..4, 0));
+
+        let rollback = session.take_rollback().expect("misprediction should roll back");
+        assert_eq!(rollback.frame, 2);
+        assert!(session.is_confirmed());
+    }
+
+    #[test]
+    fn test_checksum_mismatch_reports_desync() {
+        let mut session = RollbackSession::new(Player::One, 0, DataFingerprints::default());
+        session.handle_message(NetMessage::Hello(DataFingerprints::default()));
+        session.handle_message(inputs(0..CHECKSUM_INTERVAL + 1, 0));
+        for _ in 0..=CHECKSUM_INTERVAL {
+            step(&mut session);
+        }
+
+        session.checksum_confirmed_frames();
+        let expected = state_at(CHECKSUM_INTERVAL).checksum();
+        assert_eq!(
+            session.outgoing_checksum(),
+            Some(NetMessage::Checksum { frame: CHECKSUM_INTERVAL, value: expected })
+        );
+
+        session.handle_message(NetMessage::Checksum { frame: CHECKSUM_INTERVAL, value: expected });
+        assert_eq!(session.take_desync(), None);
+
+        session.handle_message(NetMessage::Checksum { frame: CHECKSUM_INTERVAL, value: expected ^ 1 });
+        assert_eq!(session.take_desync(), Some(CHECKSUM_INTERVAL));
+        assert_eq!(session.take_desync(), None);
+    }
+
+    #[test]
+    fn test_outgoing_resends_unacknowledged_inputs() {
+        let mut session = RollbackSession::new(Player::One, 2, DataFingerprints::default());
+        assert_eq!(session.outgoing_message(), NetMessage::Hello(DataFingerprints::default()));
+
+        session.handle_message(NetMessage::Hello(DataFingerprints::default()));
+        session.add_local_input(PlayerInput::from_bits(0b1_0000));
+
+        let NetMessage::Inputs { start_frame, inputs, .. } = session.outgoing_message() else {
+            panic!("expected inputs");
+        };
+        // Two neutral delay frames plus the real input
+        assert_eq!(start_frame, 0);
+        assert_eq!(inputs, vec![0, 0, 0b1_0000]);
+
+        session.handle_message(NetMessage::Inputs { ack: 2, start_frame: 0, inputs: Vec::new() });
+        let NetMessage::Inputs { start_frame, inputs, .. } = session.outgoing_message() else {
+            panic!("expected inputs");
+        };
+        assert_eq!(start_frame, 2);
+        assert_eq!(inputs, vec![0b1_0000]);
+    }
+}
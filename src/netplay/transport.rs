@@ -0,0 +1,157 @@
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+use bevy::prelude::*;
+use crate::netplay::protocol::NetMessage;
+
+/// Largest datagram we ever expect (64 inputs is well under this)
+const MAX_PACKET_BYTES: usize = 512;
+
+/// Simulated bad connection for local testing
+///
+/// Applied to outgoing packets only; run both peers with the same settings to
+/// degrade both directions.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShimSettings {
+    /// One-way delay added to every packet
+    pub latency_ms: u32,
+    /// Random extra delay, 0..=jitter_ms
+    pub jitter_ms: u32,
+    /// Chance to drop a packet, 0-100
+    pub loss_percent: u32,
+}
+
+impl ShimSettings {
+    pub fn is_active(&self) -> bool {
+        self.latency_ms > 0 || self.jitter_ms > 0 || self.loss_percent > 0
+    }
+}
+
+/// Delays and drops outgoing packets according to `ShimSettings`
+struct NetworkShim {
+    settings: ShimSettings,
+    queue: Vec<(Instant, Vec<u8>)>,
+    /// xorshift state - only decides packet fate, never gameplay
+    rng: u32,
+}
+
+impl NetworkShim {
+    fn new(settings: ShimSettings) -> Self {
+        Self {
+            settings,
+            queue: Vec::new(),
+            rng: 0x9E37_79B9,
+        }
+    }
+
+    fn next_random(&mut self) -> u32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        self.rng
+    }
+
+    fn push(&mut self, bytes: Vec<u8>) {
+        if self.next_random() % 100 < self.settings.loss_percent {
+            return;
+        }
+
+        let jitter = match self.settings.jitter_ms {
+            0 => 0,
+            jitter => self.next_random() % (jitter + 1),
+        };
+        let delay = Duration::from_millis((self.settings.latency_ms + jitter) as u64);
+        self.queue.push((Instant::now() + delay, bytes));
+    }
+
+    /// Remove and return every packet whose delay has elapsed
+    fn take_due(&mut self) -> Vec<Vec<u8>> {
+        let now = Instant::now();
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.queue)
+            .into_iter()
+            .partition(|(release, _)| *release <= now);
+        self.queue = pending;
+        due.into_iter().map(|(_, bytes)| bytes).collect()
+    }
+}
+
+/// Non-blocking UDP link to the other peer
+pub struct UdpTransport {
+    socket: UdpSocket,
+    peer: SocketAddr,
+    shim: Option<NetworkShim>,
+}
+
+impl UdpTransport {
+    pub fn bind(local_port: u16, peer: SocketAddr, shim: ShimSettings) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(("0.0.0.0", local_port))?;
+        socket.set_nonblocking(true)?;
+
+        if shim.is_active() {
+            info!(
+                "Netplay shim active: {}ms latency, {}ms jitter, {}% loss",
+                shim.latency_ms, shim.jitter_ms, shim.loss_percent
+            );
+        }
+
+        Ok(Self {
+            socket,
+            peer,
+            shim: shim.is_active().then(|| NetworkShim::new(shim)),
+        })
+    }
+
+    pub fn send(&mut self, message: &NetMessage) {
+        let bytes = message.encode();
+        match &mut self.shim {
+            Some(shim) => shim.push(bytes),
+            None => self.send_now(&bytes),
+        }
+    }
+
+    /// Send packets the shim has held long enough
+    pub fn flush(&mut self) {
+        let due = match &mut self.shim {
+            Some(shim) => shim.take_due(),
+            None => return,
+        };
+        for bytes in due {
+            self.send_now(&bytes);
+        }
+    }
+
+    /// Drain every packet that arrived from the peer
+    pub fn receive(&mut self) -> Vec<NetMessage> {
+        let mut messages = Vec::new();
+        let mut buffer = [0u8; MAX_PACKET_BYTES];
+
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((len, from)) if from == self.peer => {
+                    if let Some(message) = NetMessage::decode(&buffer[..len]) {
+                        messages.push(message);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                // Windows reports ICMP port-unreachable as a receive error
+                // while the peer isn't running yet - keep polling
+                Err(e) if e.kind() == ErrorKind::ConnectionReset => {}
+                Err(e) => {
+                    warn!("Netplay receive failed: {}", e);
+                    break;
+                }
+            }
+        }
+
+        messages
+    }
+
+    fn send_now(&self, bytes: &[u8]) {
+        if let Err(e) = self.socket.send_to(bytes, self.peer) {
+            if e.kind() != ErrorKind::WouldBlock {
+                warn!("Netplay send failed: {}", e);
+            }
+        }
+    }
+}
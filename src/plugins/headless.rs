@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use crate::plugins::simulation::SimulationPlugin;
+use crate::systems::game_state::GameState;
+use crate::systems::{headless, input, simulation};
+
+/// Match rules driven by controllers instead of the keyboard, with no window,
+/// rendering or gizmos
+///
+/// Used with `MinimalPlugins` by the `--headless` runner.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(SimulationPlugin)
+            .add_systems(FixedUpdate, (
+                headless::feed_controllers.before(input::update_inputs),
+                (headless::count_cornered_frames, headless::record_round_ends)
+                    .chain()
+                    .after(simulation::run_simulation_tick),
+            ).run_if(in_state(GameState::InGame)));
+    }
+}
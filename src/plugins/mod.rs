@@ -1,3 +1,8 @@
 // Plugins - Grouped systems for organization
 
 pub mod core_game;
+pub mod headless;
+pub mod netplay;
+pub mod presentation;
+pub mod replay;
+pub mod simulation;
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use crate::netplay::protocol::DataFingerprints;
+use crate::netplay::session::RollbackSession;
+use crate::netplay::transport::UdpTransport;
+use crate::netplay::{Netplay, NetplayConfig};
+use crate::systems::game_state::GameState;
+use crate::systems::loading::StartState;
+use crate::systems::netplay;
+use crate::systems::simulation::SimulationDriver;
+
+/// Online play - replaces the local simulation driver with rollback netcode
+///
+/// Added on top of `CoreGamePlugin` when the game is started with `--netplay`.
+pub struct NetplayPlugin {
+    pub config: NetplayConfig,
+}
+
+impl Plugin for NetplayPlugin {
+    fn build(&self, app: &mut App) {
+        let config = self.config.clone();
+        let transport = UdpTransport::bind(config.local_port, config.peer, config.shim)
+            .unwrap_or_else(|e| panic!("Netplay: failed to bind UDP port {}: {}", config.local_port, e));
+
+        app
+            .insert_resource(Netplay {
+                transport,
+                // Replaced with the loaded data's fingerprints when the match starts
+                session: RollbackSession::new(config.local_player, config.input_delay, DataFingerprints::default()),
+            })
+            .insert_resource(config)
+            .insert_resource(SimulationDriver::Netplay)
+            // Skip character select (both peers use the default character)
+            .insert_resource(StartState(GameState::InGame))
+            .add_systems(OnEnter(GameState::InGame), netplay::start_netplay_session)
+            .add_systems(FixedUpdate, netplay::netplay_tick.run_if(in_state(GameState::InGame)));
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use crate::components::character::{Character, Player};
+use crate::systems::{attack, breath, chain, collision, corner, damage, decisive, evade, game_state, guard, health, hitstop, hot_reload, initiative, jump, momentum, movement, pressure, stance, stumble, tenuki, ui, visual_effects};
+
+/// Give newly spawned fighters their rectangle sprite
+///
+/// Fighters are spawned by the simulation without any rendering components;
+/// this also covers fighters respawned by a `SimState` restore.
+pub fn attach_fighter_sprites(
+    mut commands: Commands,
+    query: Query<(Entity, &Player), Added<Character>>,
+) {
+    for (entity, player) in query.iter() {
+        let color = match player {
+            Player::One => Color::srgb(0.9, 0.2, 0.2),  // Red
+            Player::Two => Color::srgb(0.2, 0.4, 0.9),  // Blue
+        };
+
+        commands.entity(entity).insert((
+            Sprite {
+                color,
+                custom_size: Some(Vec2::new(100.0, 200.0)),  // Increased by 25%
+                ..default()
+            },
+            Handle::<Image>::default(),
+            VisibilityBundle::default(),
+        ));
+    }
+}
+
+/// Presentation - sprites, gizmos, UI and debug output for a match
+///
+/// Reads the state `SimulationPlugin` produces and never feeds back into the
+/// rules, so a match plays out the same with or without it.
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, attach_fighter_sprites.run_if(game_state::in_match))
+            .add_systems(Update, (
+                hot_reload::show_reload_notice,
+                hot_reload::expire_reload_notice,
+            ).chain())
+            .add_systems(Update, (
+                // Visual feedback - Part 1
+                hitstop::hitstop_screen_shake,    // Screen shake during hitstop
+                hitstop::cleanup_hitstop_camera,  // Reset camera after hitstop
+                attack::visualize_attack_phases,
+                attack::visualize_attack_direction,  // Phase 4.5: Show attack direction
+                guard::visualize_blocking,
+                guard::parry_flash_effect,
+                damage::hit_flash_feedback,
+                health::visualize_health_state,  // Phase 4: Visual health state
+                breath::visualize_decisive_blow_availability,  // Phase 4: Decisive blow danger
+                breath::visualize_desperation,   // Desperation red aura
+                decisive::visualize_decisive_windup, // Killing posture
+                decisive::kill_visual,           // Ink and the cut on a kill
+                jump::visualize_jump,            // Shadow under a jumping fighter
+                stance::visualize_stance,        // Held blade in a stance
+                corner::visualize_corner,        // Lit wall behind a cornered fighter
+                ui::render_breath_indicators,    // Phase 4: Breath UI
+                ui::render_health_bars,          // Phase 4: Health bars
+                ui::render_final_stand_indicators, // Final Stand marker
+            ).run_if(game_state::in_match))
+            .add_systems(Update, (
+                // Visual feedback - Part 2
+                ui::render_round_timer,          // Phase 4: Round timer
+                ui::render_round_text_indicator, // Phase 4: Round text
+                ui::render_victory_screen,       // Phase 4: Victory screen
+                evade::visualize_evade,
+                evade::cleanup_evade_visuals,
+                movement::visualize_dash_cooldown, // Dash cooldown indicator
+                initiative::visualize_initiative,
+                pressure::visualize_pressure,
+                momentum::visualize_momentum,
+                chain::visualize_chain_window,
+                stumble::visualize_stumble_direction,  // Phase 5.3: Stumble direction arrow
+                stumble::visualize_stumble_state,      // Phase 5.3: Stumble visual feedback
+                stumble::tech_flash_effect,            // Phase 5.3: Tech flash visual
+                stumble::wall_bounce_visual,           // Phase 5.3: Wall bounce impact effect
+                stumble::spike_finisher_visual,        // Phase 5.3 Phase 4: Spike finisher impact effect
+                tenuki::tenuki_visual,                 // Tenuki absorb ring
+                visual_effects::combo_hit_flash,  // Combo hit flash escalation
+                visual_effects::debug_combo_hits, // Debug combo tracking
+            ).run_if(game_state::in_match))
+            .add_systems(Update, (
+                // Debug
+                movement::debug_character_state,
+                attack::debug_attack_state,
+                guard::debug_guard_meter,
+                damage::debug_hit_events,
+                health::debug_health_display,  // Phase 4: Debug health
+                breath::debug_breath_display,   // Phase 4: Debug breath
+                initiative::debug_initiative,
+                pressure::debug_pressure,
+                momentum::debug_momentum,
+                chain::debug_chain_state,
+                stumble::debug_stumble_state,   // Phase 5.3: Debug stumble
+                tenuki::debug_tenuki_events,
+                decisive::debug_final_parry_events,
+                decisive::debug_decisive_cut_events,
+                collision::debug_draw_boxes,
+            ).run_if(game_state::in_match));
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+use crate::data::replay::Replay;
+use crate::plugins::simulation::spawn_players;
+use crate::systems::game_state::GameState;
+use crate::systems::loading::StartState;
+use crate::systems::playback::{self, ReplayPlayback};
+use crate::systems::simulation::{self, SimulationDriver};
+
+/// Replay playback - re-runs a recorded match from its inputs
+///
+/// Added on top of `CoreGamePlugin` when the game is started with `--replay`.
+pub struct ReplayPlaybackPlugin {
+    pub replay: Replay,
+}
+
+impl Plugin for ReplayPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ReplayPlayback::new(self.replay.clone()))
+            .insert_resource(SimulationDriver::Replay)
+            // Replays are loaded from the command line, so go straight to playback
+            .insert_resource(StartState(GameState::Replay))
+            .add_systems(OnEnter(GameState::Replay), (
+                spawn_players,
+                simulation::reset_simulation,
+                playback::start_playback,
+                playback::setup_playback_hud,
+            ).chain())
+            .add_systems(FixedUpdate, playback::playback_tick.run_if(in_state(GameState::Replay)))
+            .add_systems(Update, (
+                playback::playback_controls,
+                playback::update_playback_hud,
+            ).chain().run_if(in_state(GameState::Replay)));
+    }
+}
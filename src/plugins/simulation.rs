@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+use crate::components::breath::RoundEndEvent;
+use crate::data::character::{CharacterData, CharacterLoader, Characters};
+use crate::data::game_config::{GameConfig, GameConfigLoader};
+use crate::events::combat_events::*;
+use crate::systems::{attack, breath, chain, collision, corner, damage, decisive, evade, game_state, guard, health, hitstop, hot_reload, initiative, input, jump, loading, momentum, movement, pressure, simulation, stance, stumble, sync_test, tenuki};
+use crate::systems::game_state::GameState;
+use simulation::{SimSet, SimulationTick};
+
+/// Spawn players when a match starts (live or replayed)
+pub fn spawn_players(mut commands: Commands, characters: Res<Characters>, config: Res<GameConfig>) {
+    use crate::components::breath::*;
+    use crate::components::character::*;
+    use crate::components::combo::InputBuffer;
+    use crate::components::combat::*;
+    use crate::components::guard::*;
+    use crate::components::health::*;
+    use crate::components::initiative::*;
+    use crate::components::movelist::*;
+    use crate::components::state::*;
+    use crate::systems::chain::ChainState;
+    use crate::systems::momentum::Momentum;
+    use crate::systems::pressure::Pressure;
+
+    info!("Spawning players for match...");
+
+    // Both players use the default character until character select picks one
+    let movelist = characters
+        .movelist(DEFAULT_CHARACTER)
+        .cloned()
+        .expect("characters are loaded before leaving GameState::Loading");
+
+    // Initialize match state (starts with countdown)
+    commands.insert_resource(MatchState::with_timers(config.round_frames, config.countdown_frames));
+
+    // Spawn Player 1 (left side)
+    let player1 = commands.spawn((
+        Character,
+        Player::One,
+        CharacterState::Idle,
+        MaxSpeed(config.walk_speed),
+        Velocity::default(),
+        Hurtbox::default(),
+        GuardMeter::default(),
+        Initiative::default(),
+        Pressure::default(),
+        ChainState::default(),
+        Momentum::with_decay_threshold(config.momentum_decay_frames),
+        Health::new(config.max_health),
+        Breath::new(config.breaths),
+        movelist.clone(),
+        TransformBundle::from_transform(Transform::from_xyz(-config.spawn_offset, 0.0, 0.0)),
+    )).id();
+
+    // Add InputBuffer, Facing, Corner and Pushbox separately to avoid bundle size limit
+    commands.entity(player1).insert((InputBuffer::default(), Facing::Right, Corner::default(), Pushbox::default()));
+
+    // Spawn Player 2 (right side)
+    let player2 = commands.spawn((
+        Character,
+        Player::Two,
+        CharacterState::Idle,
+        MaxSpeed(config.walk_speed),
+        Velocity::default(),
+        Hurtbox::default(),
+        GuardMeter::default(),
+        Initiative::default(),
+        Pressure::default(),
+        ChainState::default(),
+        Momentum::with_decay_threshold(config.momentum_decay_frames),
+        Health::new(config.max_health),
+        Breath::new(config.breaths),
+        movelist,
+        TransformBundle::from_transform(Transform::from_xyz(config.spawn_offset, 0.0, 0.0)),
+    )).id();
+
+    // Add InputBuffer, Facing, Corner and Pushbox separately to avoid bundle size limit
+    commands.entity(player2).insert((InputBuffer::default(), Facing::Left, Corner::default(), Pushbox::default()));
+}
+
+/// Despawn players when exiting InGame state (for rematch/reselect)
+pub fn despawn_players(
+    mut commands: Commands,
+    query: Query<Entity, With<crate::components::character::Character>>,
+) {
+    info!("Despawning players for clean state...");
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Match rules - state, resources, events, fighter lifecycle and the
+/// fixed-tick simulation
+///
+/// Only touches gameplay components: no window, sprites, gizmos or keyboard,
+/// so it runs under `MinimalPlugins` plus `AssetPlugin` (headless runner,
+/// tools, tests, servers). Tuning comes from `assets/data/game_config.ron` and
+/// movelists from character files under `assets/data/characters/`, both
+/// loaded while in `GameState::Loading`.
+/// Whatever fills `LatchedInputs` before each tick controls the fighters.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // State machine
+            .init_state::<GameState>()
+
+            // Fixed 60 Hz simulation clock
+            .insert_resource(Time::<Fixed>::from_hz(simulation::SIMULATION_HZ))
+
+            // Game config and character files
+            .init_asset::<GameConfig>()
+            .init_asset_loader::<GameConfigLoader>()
+            .init_asset::<CharacterData>()
+            .init_asset_loader::<CharacterLoader>()
+            .init_resource::<Characters>()
+            .init_resource::<loading::StartState>()
+            .add_systems(Startup, loading::load_game_data)
+            .add_systems(Update, (
+                loading::finish_loading_config,
+                loading::finish_loading,
+            ).chain().run_if(in_state(GameState::Loading)))
+
+            // Hot reload: edited files are swapped in at the next neutral moment
+            .init_resource::<hot_reload::PendingReload>()
+            .add_event::<hot_reload::DataReloaded>()
+            .add_systems(Update, (
+                hot_reload::detect_character_changes,
+                hot_reload::detect_config_changes,
+                hot_reload::apply_pending_reload
+                    .run_if(not(game_state::in_match).or_else(hot_reload::can_swap_in_match)),
+            ).chain().run_if(not(in_state(GameState::Loading))))
+
+            // Resources
+            .init_resource::<input::CurrentInputs>()
+            .init_resource::<input::LatchedInputs>()
+            .init_resource::<simulation::SimFrame>()
+            .init_resource::<simulation::SimulationDriver>()
+            .init_resource::<simulation::MatchSeed>()
+
+            // Events
+            .add_event::<HitEvent>()
+            .add_event::<ParryEvent>()
+            .add_event::<GuardBreakEvent>()
+            .add_event::<GrabEvent>()
+            .add_event::<tenuki::TenukiEvent>()
+            .add_event::<decisive::FinalParryEvent>()
+            .add_event::<decisive::DecisiveCutEvent>()
+            .add_event::<RoundEndEvent>()  // Phase 4: Round end event
+
+            // Fighters exist only during a match
+            .add_systems(OnEnter(GameState::InGame), (
+                spawn_players,
+                simulation::reset_simulation,
+                sync_test::reset_sync_test,
+            ))
+            .add_systems(OnExit(GameState::InGame), despawn_players)
+
+            // Fixed-timestep simulation: gameplay advances exactly one frame per fixed tick
+            .add_systems(FixedUpdate, (
+                input::update_inputs,
+                simulation::run_simulation_tick,
+                sync_test::sync_test_tick.run_if(resource_exists::<sync_test::SyncTest>),
+            ).chain()
+                .run_if(in_state(GameState::InGame))
+                .run_if(resource_equals(simulation::SimulationDriver::Local)))
+            .configure_sets(SimulationTick, (
+                SimSet::Input,
+                SimSet::Progression,
+                SimSet::Physics,
+                SimSet::Reactions,
+                SimSet::Bookkeeping,
+            ).chain())
+
+            // Simulation systems - split into groups due to Bevy tuple limits
+            .add_systems(SimulationTick, (
+                // Input and movement
+                chain::record_inputs_to_buffer,     // Buffer inputs for combo execution
+                chain::age_input_buffers,           // Age buffered inputs each frame
+                movement::update_facing,            // Turn to face the opponent after crossing
+                movement::process_movement_input,
+                movement::handle_dash_input,        // Dash input handling
+                decisive::handle_decisive_input,    // Heavy becomes the Decisive Blow when open
+                attack::handle_attack_input,
+                stance::handle_stance_input,        // Hold into a stance, release or cancel it
+                guard::handle_block_input,
+                decisive::tighten_final_parry,      // Final Parry window against a wind-up
+                evade::handle_evade_input,
+                jump::handle_jump_input,            // Up jumps (Shift + Up evades)
+                jump::handle_air_attack_input,      // The one aerial attack per jump
+                chain::handle_chain_input,
+                movement::update_movement_state,
+            ).chain().in_set(SimSet::Input))
+            .add_systems(SimulationTick, (
+                // Hitstop processing - MUST run first before state progression
+                hitstop::process_hitstop,
+                // State progression
+                breath::tick_round_countdown,      // Phase 4: Round countdown
+                breath::tick_round_timer,          // Phase 4: Round timer
+                attack::progress_attack_phases,
+                guard::progress_stun,              // Stagger, hitstun and blockstun
+                guard::progress_parry,
+                guard::tick_punish_window,         // Perfect parry punish window
+                decisive::resolve_decisive_blows,  // Wind-up ends: kill, Final Parry or whiff
+                evade::progress_evade,
+                movement::tick_dash_cooldown,      // Dash cooldown
+                initiative::tick_initiative,
+                momentum::tick_momentum,
+                chain::manage_chain_window,
+                stumble::handle_tech_input,        // Phase 5.3: Handle tech during stumble
+                stumble::process_stumble,          // Phase 5.3: Tick stumble duration
+                stumble::tick_impact_flashes,      // Tech/wall bounce/spike flash timers
+                tenuki::tick_tenuki,               // Tenuki ring timer, armor reset
+                attack::update_attack_boxes,
+                movement::initiate_attack_movement, // Phase 4.5: Start attack movement
+                movement::cleanup_attack_movement,  // Phase 4.5: Clean up finished movement
+            ).chain().in_set(SimSet::Progression))
+            .add_systems(SimulationTick, (
+                // Physics and collision
+                movement::apply_dash_movement,      // Apply dash movement
+                movement::apply_attack_movement,    // Phase 4.5: Apply attack-based movement
+                movement::apply_velocity,
+                corner::apply_pushback,             // Slide away from a hit or block
+                jump::progress_jump,                // Prejump, take-off and landing recovery
+                jump::apply_jump_arc,               // Jump arc under gravity, touching down
+                movement::clamp_to_stage,
+                movement::resolve_pushboxes,        // Body collision between fighters
+                corner::update_corners,             // Who has their back to the wall
+                stumble::detect_wall_bounce,        // Phase 5.3: Wall bounce detection
+                collision::detect_hits,
+            ).chain().in_set(SimSet::Physics))
+            .add_systems(SimulationTick, (
+                // Reactions - Part 1
+                hitstop::apply_hitstop_on_hit,          // Apply hitstop when hits connect
+                tenuki::absorb_light_hits,              // Heavy absorbs a Light in startup
+                stumble::apply_stumble_on_hit,          // Phase 5.3: Apply stumble from launchers
+                stumble::extend_stumble_on_hit,         // Phase 5.3: Extend stumble with extenders
+                stumble::handle_spike_finisher,         // Phase 5.3 Phase 4: Spike finishers on stumbling opponents
+                jump::knock_out_of_air,                 // Anti-air: a jumper hit drops straight down
+                guard::check_parry_success,
+                guard::apply_blockstun,                 // Blocked hits hold the defender in block
+                damage::apply_hit_reactions,
+                health::apply_health_damage,            // Phase 4: Apply damage to health
+                health::apply_movement_speed_modifier,  // Phase 4: Health state movement penalty
+                health::apply_frame_advantage_penalty,  // Phase 4: Health state frame penalty
+                health::restrict_pressure_by_health,    // Phase 4: Health state pressure cap
+                health::restrict_momentum_by_health,    // Phase 4: Health state momentum restriction
+                decisive::tick_kill_hold,               // Hold the kill, then end the round
+                breath::check_timeout,                  // Phase 4: Check for timeout
+                breath::handle_round_end,               // Phase 4: Handle round end
+                breath::check_match_victory,            // Phase 4: Check match victory
+            ).chain().in_set(SimSet::Reactions))
+            .add_systems(SimulationTick, (
+                // Reactions - Part 2
+                guard::fill_guard_on_block,
+                corner::push_back_on_hit,           // Cornered defenders push the attacker out
+                stance::drain_guard_in_stance,      // Holding a stance costs guard
+                guard::check_guard_break,
+                guard::drain_guard_meter,
+                initiative::apply_frame_advantage,
+                initiative::apply_parry_advantage,
+                pressure::build_pressure,
+                pressure::apply_pressure_movement_bonus,
+                pressure::drain_pressure_passive,
+                momentum::build_momentum_on_hit,
+                momentum::build_momentum_on_parry,
+                chain::mark_chainable_on_hit,
+            ).chain().in_set(SimSet::Bookkeeping));
+    }
+}
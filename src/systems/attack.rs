@@ -1,21 +1,23 @@
 use bevy::prelude::*;
-use crate::components::character::Player;
-use crate::components::combat::{AttackProperty, Hitbox};
-use crate::components::movelist::{AttackDirection, Movelist};
+use crate::components::character::{Facing, Player};
+use crate::components::combat::{Hitbox, Hurtbox};
+use crate::components::movelist::{MoveData, Movelist};
 use crate::components::state::*;
 use crate::systems::input::CurrentInputs;
 
 /// Progress attack animations through phases (Startup → Active → Recovery → Idle)
+///
+/// Phase lengths come from the move being performed in the fighter's `Movelist`.
 pub fn progress_attack_phases(
     mut commands: Commands,
-    mut query: Query<(Entity, &mut CharacterState, &mut StateTimer)>,
+    mut query: Query<(Entity, &mut CharacterState, &mut StateTimer, &Movelist)>,
 ) {
     let count = query.iter().count();
     if count > 0 {
         debug!("progress_attack_phases: Found {} entities with StateTimer", count);
     }
 
-    for (entity, mut state, mut timer) in query.iter_mut() {
+    for (entity, mut state, mut timer, movelist) in query.iter_mut() {
         debug!("Checking entity {:?}, state: {:?}", entity, state);
         if let CharacterState::Attacking { attack_type, direction, phase, .. } = *state {
             let before_tick = timer.elapsed;
@@ -24,69 +26,87 @@ pub fn progress_attack_phases(
                 before_tick, timer.target, timer.elapsed, timer.target,
                 attack_type, direction, phase);
 
-            if timer.is_complete() {
-                // Transition to next phase
-                match phase {
-                    AttackPhase::Startup => {
-                        // Startup complete → Enter Active phase
-                        let attack_data = get_attack_data(attack_type);
-                        *state = CharacterState::Attacking {
-                            attack_type,
-                            direction,
-                            phase: AttackPhase::Active,
-                        };
-                        timer.reset(attack_data.active_frames);
+            if !timer.is_complete() {
+                continue;
+            }
 
-                        debug!(
-                            "Attack phase: Startup → Active ({} frames)",
-                            attack_data.active_frames
-                        );
-                    }
-                    AttackPhase::Active => {
-                        // Active complete → Enter Recovery phase
-                        let attack_data = get_attack_data(attack_type);
-                        *state = CharacterState::Attacking {
-                            attack_type,
-                            direction,
-                            phase: AttackPhase::Recovery,
-                        };
-                        timer.reset(attack_data.recovery_frames);
+            let next_phase = match phase {
+                AttackPhase::Startup => AttackPhase::Active,
+                AttackPhase::Active => AttackPhase::Recovery,
+                AttackPhase::Recovery => {
+                    // Recovery complete → Return to Idle
+                    *state = CharacterState::Idle;
+                    commands.entity(entity).remove::<StateTimer>();
 
-                        debug!(
-                            "Attack phase: Active → Recovery ({} frames)",
-                            attack_data.recovery_frames
-                        );
-                    }
-                    AttackPhase::Recovery => {
-                        // Recovery complete → Return to Idle
-                        *state = CharacterState::Idle;
-                        commands.entity(entity).remove::<StateTimer>();
-
-                        info!("Attack phase: Recovery → Idle, REMOVING StateTimer from entity {:?}", entity);
-                    }
+                    info!("Attack phase: Recovery → Idle, REMOVING StateTimer from entity {:?}", entity);
+                    continue;
                 }
-            }
+            };
+
+            let Some(move_data) = movelist.get_move(attack_type, direction) else {
+                // Only possible if the movelist changed mid-attack; drop the attack
+                warn!("No move found for {:?} {:?}, ending attack", attack_type, direction);
+                *state = CharacterState::Idle;
+                commands.entity(entity).remove::<StateTimer>();
+                continue;
+            };
+
+            // Startup → Active, Active → Recovery
+            *state = CharacterState::Attacking {
+                attack_type,
+                direction,
+                phase: next_phase,
+            };
+            timer.reset(move_data.phase_frames(next_phase));
+
+            debug!(
+                "Attack phase: {:?} → {:?} ({} frames)",
+                phase, next_phase, timer.target
+            );
         }
     }
 }
 
-/// Activate hitboxes when entering Active phase
-pub fn activate_hitboxes(
-    mut query: Query<(&CharacterState, &mut Hitbox), Changed<CharacterState>>,
+/// Shape hitboxes and hurtboxes from the move being performed
+///
+/// Hitboxes are out only in the active phase, with the move's boxes for the
+/// current active frame; the hurtbox takes the move's shape (if it has one)
+/// for the whole attack. Shapes are authored facing right and mirrored for
+/// a fighter facing left.
+#[allow(clippy::type_complexity)]
+pub fn update_attack_boxes(
+    mut query: Query<(&CharacterState, Option<&StateTimer>, &Movelist, &Facing, &mut Hurtbox, Option<&mut Hitbox>)>,
 ) {
-    for (state, mut hitbox) in query.iter_mut() {
-        match state {
-            CharacterState::Attacking {
-                phase: AttackPhase::Active,
-                ..
-            } => {
-                hitbox.activate();
-                debug!("Hitbox activated");
+    for (state, timer, movelist, facing, mut hurtbox, hitbox) in query.iter_mut() {
+        let move_data = state.move_id().and_then(|move_id| movelist.get(move_id));
+        let facing_sign = facing.sign();
+
+        let standing = Hurtbox::default();
+        let shape = move_data
+            .and_then(|data| data.hurtbox)
+            .map(|shape| Hurtbox::new(shape.rect(facing_sign)))
+            .unwrap_or(standing);
+        if hurtbox.rect != shape.rect {
+            *hurtbox = shape;
+        }
+
+        let Some(mut hitbox) = hitbox else {
+            continue;
+        };
+        match (state, move_data) {
+            (CharacterState::Attacking { phase: AttackPhase::Active, .. }, Some(move_data)) => {
+                let frame = timer.map(|t| t.elapsed).unwrap_or(0);
+                hitbox.rects = move_data.hitbox_rects(frame, facing_sign);
+                if !hitbox.active {
+                    hitbox.activate();
+                    debug!("Hitbox activated ({} boxes)", hitbox.rects.len());
+                }
             }
             _ => {
                 // Deactivate hitbox in all other states
                 if hitbox.active {
                     hitbox.deactivate();
+                    hitbox.rects.clear();
                     debug!("Hitbox deactivated");
                 }
             }
@@ -134,26 +154,13 @@ pub fn visualize_attack_phases(
     }
 }
 
-/// Helper function to get attack data for an attack type
-fn get_attack_data(attack_type: AttackType) -> AttackData {
-    match attack_type {
-        AttackType::Light => AttackData::light(),
-        AttackType::Heavy => AttackData::heavy(),
-        AttackType::Grab => AttackData::grab(),
-    }
-}
-
 /// Handle attack button inputs and initiate attacks
 pub fn handle_attack_input(
     mut commands: Commands,
     inputs: Res<CurrentInputs>,
-    mut query: Query<(Entity, &Player, &Transform, &mut CharacterState, &Movelist)>,
-    opponent_query: Query<&Transform, (With<Player>, Without<CharacterState>)>,
+    mut query: Query<(Entity, &Player, &Facing, &mut CharacterState, &Movelist)>,
 ) {
-    // Get both player positions for direction calculation
-    let positions: Vec<(Entity, Vec2)> = query.iter().map(|(e, _, t, _, _)| (e, t.translation.xy())).collect();
-
-    for (entity, player, transform, mut state, movelist) in query.iter_mut() {
+    for (entity, player, facing, mut state, movelist) in query.iter_mut() {
         // Can only attack from Idle or Walking state
         if !matches!(*state, CharacterState::Idle | CharacterState::Walking) {
             continue;
@@ -178,30 +185,23 @@ pub fn handle_attack_input(
 
         // Initiate attack if button pressed
         if let Some(attack_type) = attack_type {
-            // Get opponent position for direction calculation
-            let player_x = transform.translation.x;
-            let opponent_x = positions.iter()
-                .find(|(e, _)| *e != entity)
-                .map(|(_, pos)| pos.x)
-                .unwrap_or(0.0);
-
-            // Get attack direction from input
-            let direction = input.get_attack_direction(*player, opponent_x, player_x);
+            // Get attack direction from input, relative to facing
+            let direction = input.get_attack_direction(*facing);
 
-            // Get move data from movelist
-            let move_data = match movelist.get_move(attack_type, direction) {
-                Some(data) => data,
-                None => {
-                    warn!("No move found for {:?} {:?}, using neutral", attack_type, direction);
-                    movelist.get_move(attack_type, AttackDirection::Neutral)
-                        .expect("Neutral move must exist!")
-                }
+            // Resolve the move once (falling back to neutral); the state
+            // records it so later phases read the same move data
+            let Some(move_id) = movelist.resolve(attack_type, direction) else {
+                warn!("No {:?} move in movelist, ignoring input", attack_type);
+                continue;
+            };
+            let Some(move_data) = movelist.get(move_id) else {
+                continue;
             };
 
             // Enter Attacking state
             *state = CharacterState::Attacking {
                 attack_type,
-                direction,
+                direction: move_id.direction,
                 phase: AttackPhase::Startup,
             };
 
@@ -211,18 +211,12 @@ pub fn handle_attack_input(
 
             // Add timer for startup phase
             let timer = StateTimer::new(move_data.startup_frames);
-            info!("Adding StateTimer: target={}, move={:?}", timer.target, direction);
+            info!("Adding StateTimer: target={}, move={}", timer.target, move_data.name);
             commands.entity(entity).insert(timer);
 
             // Add/update hitbox component with move data
-            let hitbox = Hitbox {
-                rect: Rect::from_center_size(move_data.hitbox_offset, move_data.hitbox_size),
-                active: false,
-                damage: move_data.damage as u8,
-                properties: move_data.properties.clone(),
-            };
             if let Some(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.insert(hitbox);
+                entity_commands.insert(create_hitbox(move_data));
             }
 
             info!(
@@ -238,34 +232,9 @@ pub fn handle_attack_input(
     }
 }
 
-/// Create a hitbox for the given attack type
-pub fn create_hitbox(attack_type: AttackType) -> Hitbox {
-    match attack_type {
-        AttackType::Light => {
-            // Light attack: small, fast hitbox in front of character
-            let rect = Rect::from_center_size(
-                Vec2::new(50.0, 0.0),  // 50 pixels in front
-                Vec2::new(40.0, 60.0), // Width x Height
-            );
-            Hitbox::new(rect, 1) // 1 damage (one state)
-        }
-        AttackType::Heavy => {
-            // Heavy attack: larger, longer range hitbox
-            let rect = Rect::from_center_size(
-                Vec2::new(60.0, 0.0),  // 60 pixels in front
-                Vec2::new(60.0, 80.0), // Larger hitbox
-            );
-            Hitbox::new(rect, 2).with_properties(vec![AttackProperty::LightArmor])
-        }
-        AttackType::Grab => {
-            // Grab: short range, unblockable
-            let rect = Rect::from_center_size(
-                Vec2::new(40.0, 0.0),  // 40 pixels in front (close range)
-                Vec2::new(50.0, 70.0),
-            );
-            Hitbox::new(rect, 0).with_properties(vec![AttackProperty::Unblockable])
-        }
-    }
+/// Create the hitbox of a move, inactive and empty until its active phase
+pub fn create_hitbox(move_data: &MoveData) -> Hitbox {
+    Hitbox::new(Vec::new()).with_properties(move_data.properties.clone())
 }
 
 /// Debug system to log attack state changes
@@ -317,6 +286,12 @@ pub fn visualize_attack_direction(
                 AttackDirection::Down => {
                     (pos + Vec2::new(0.0, -60.0), Color::srgb(1.0, 0.5, 0.0)) // Orange
                 }
+                AttackDirection::Air => {
+                    (pos + Vec2::new(0.0, -60.0), Color::srgb(0.3, 1.0, 0.8)) // Teal
+                }
+                AttackDirection::Stance => {
+                    (pos + Vec2::new(indicator_distance, 60.0), Color::srgb(1.0, 1.0, 1.0)) // White
+                }
                 AttackDirection::Back => {
                     (pos + Vec2::new(-indicator_distance, 60.0), Color::srgb(0.5, 0.5, 1.0)) // Light blue
                 }
@@ -324,7 +299,7 @@ pub fn visualize_attack_direction(
 
             // Draw arrow or indicator
             match direction {
-                AttackDirection::Forward => {
+                AttackDirection::Forward | AttackDirection::Stance => {
                     // Right arrow
                     let arrow_size = 15.0;
                     gizmos.line_2d(
@@ -343,7 +318,7 @@ pub fn visualize_attack_direction(
                         indicator_color,
                     );
                 }
-                AttackDirection::Down => {
+                AttackDirection::Down | AttackDirection::Air => {
                     // Down arrow
                     let arrow_size = 15.0;
                     gizmos.line_2d(
@@ -1,58 +1,7 @@
 use bevy::prelude::*;
-use crate::components::breath::{Breath, RoundEndEvent, RoundEndReason};
-use crate::components::health::Health;
-use crate::components::state::AttackType;
-use crate::events::combat_events::HitEvent;
-
-/// Check for decisive blow conditions and trigger breath loss
-pub fn check_decisive_blow(
-    mut hit_events: EventReader<HitEvent>,
-    mut round_end_events: EventWriter<RoundEndEvent>,
-    health_query: Query<&Health>,
-) {
-    for event in hit_events.read() {
-        // Skip if hit was blocked
-        if event.was_blocked {
-            continue;
-        }
-
-        // Get defender's health
-        let defender_health = match health_query.get(event.defender) {
-            Ok(health) => health,
-            Err(_) => continue,
-        };
-
-        // Check if defender is in Broken state (vulnerable)
-        if !defender_health.is_broken() {
-            continue;
-        }
-
-        // Check if attack is Heavy or Grab (not Light)
-        if !matches!(event.attack_type, AttackType::Heavy | AttackType::Grab) {
-            continue;
-        }
-
-        // Get attacker's health
-        let attacker_health = match health_query.get(event.attacker) {
-            Ok(health) => health,
-            Err(_) => continue,
-        };
-
-        // Check if attacker is healthy enough (Whole or Cut)
-        if !attacker_health.can_decisive_blow() {
-            continue;
-        }
-
-        // ALL CONDITIONS MET - DECISIVE BLOW!
-        info!("DECISIVE BLOW! {:?} defeated with {:?}", event.attack_type, defender_health.state);
-
-        // Trigger round end event (breath loss handled by handle_round_end)
-        round_end_events.send(RoundEndEvent {
-            winner: event.attacker,
-            reason: RoundEndReason::DecisiveBlow,
-        });
-    }
-}
+use crate::components::breath::{Breath, Desperation, RoundEndEvent, RoundEndReason};
+use crate::components::health::{FinalStand, Health};
+use crate::data::game_config::GameConfig;
 
 /// Visual indicator that decisive blow is available
 pub fn visualize_decisive_blow_availability(
@@ -87,15 +36,28 @@ pub fn visualize_decisive_blow_availability(
     }
 }
 
+/// Subtle red aura around a desperate player
+pub fn visualize_desperation(
+    mut gizmos: Gizmos,
+    query: Query<(&Transform, Has<FinalStand>), With<Desperation>>,
+) {
+    for (transform, final_stand) in query.iter() {
+        let pos = transform.translation.xy();
+        let alpha = if final_stand { 0.6 } else { 0.35 };
+
+        gizmos.rect_2d(pos, 0.0, Vec2::new(116.0, 216.0), Color::srgba(0.9, 0.05, 0.05, alpha));
+        gizmos.rect_2d(pos, 0.0, Vec2::new(124.0, 224.0), Color::srgba(0.6, 0.0, 0.0, alpha * 0.5));
+    }
+}
+
 /// Update countdown timer before round starts
 pub fn tick_round_countdown(
     mut match_state: ResMut<crate::components::breath::MatchState>,
-    time: Res<Time>,
 ) {
-    if !match_state.round_active && match_state.countdown > 0.0 && !match_state.match_over {
-        match_state.countdown -= time.delta_seconds();
+    if !match_state.round_active && match_state.countdown_frames > 0 && !match_state.match_over {
+        match_state.countdown_frames -= 1;
 
-        if match_state.countdown <= 0.0 {
+        if match_state.countdown_frames == 0 {
             // Start the round!
             match_state.start_round();
             info!("FIGHT! Round {} begins!", match_state.round_number);
@@ -106,10 +68,9 @@ pub fn tick_round_countdown(
 /// Update round timer during active round
 pub fn tick_round_timer(
     mut match_state: ResMut<crate::components::breath::MatchState>,
-    time: Res<Time>,
 ) {
     if match_state.round_active {
-        match_state.round_time -= time.delta_seconds();
+        match_state.round_frames = match_state.round_frames.saturating_sub(1);
 
         if match_state.is_timeout() {
             info!("TIMEOUT! Round {} ended by time", match_state.round_number);
@@ -119,12 +80,15 @@ pub fn tick_round_timer(
 }
 
 /// Check for timeout and determine winner
+///
+/// A kill already being held (`decisive::Slain`) ends the round itself.
 pub fn check_timeout(
     match_state: Res<crate::components::breath::MatchState>,
     mut round_end_events: EventWriter<RoundEndEvent>,
     query: Query<(Entity, &Health, &crate::components::character::Player)>,
+    slain: Query<(), With<crate::systems::decisive::Slain>>,
 ) {
-    if match_state.round_active && match_state.is_timeout() {
+    if match_state.round_active && match_state.is_timeout() && slain.is_empty() {
         // Find player with more health
         let mut players: Vec<_> = query.iter().collect();
 
@@ -158,8 +122,14 @@ pub fn check_timeout(
 }
 
 /// Handle round end: reset positions, health, states
+///
+/// Also enters or clears Desperation once the Breath score changes, after
+/// the reset so a desperate player starts the round with Final Stand.
 pub fn handle_round_end(
+    mut commands: Commands,
     mut round_end_events: EventReader<RoundEndEvent>,
+    desperate: Query<(), With<Desperation>>,
+    config: Res<GameConfig>,
     mut match_state: ResMut<crate::components::breath::MatchState>,
     mut query: Query<(
         Entity,
@@ -207,8 +177,8 @@ pub fn handle_round_end(
 
             // Reset position based on player
             let spawn_x = match *player {
-                crate::components::character::Player::One => -300.0,
-                crate::components::character::Player::Two => 300.0,
+                crate::components::character::Player::One => -config.spawn_offset,
+                crate::components::character::Player::Two => config.spawn_offset,
             };
             transform.translation = Vec3::new(spawn_x, 0.0, 0.0);
 
@@ -218,9 +188,35 @@ pub fn handle_round_end(
             pressure.intensity = 0;
             momentum.reset();
 
+            // An unused Final Stand doesn't carry over, and nobody starts mid-jump or sliding
+            commands.entity(entity).remove::<(FinalStand, crate::systems::jump::JumpArc, crate::systems::corner::Pushback)>();
+
             debug!("Player {:?} reset for next round", player);
         }
 
+        // Desperation: down 0-2 against an opponent who hasn't lost a Breath.
+        // Every desperate round starts with Final Stand, including rounds after
+        // the first (the reset above took the old one away)
+        let breaths: Vec<(Entity, Breath, crate::components::character::Player)> = query
+            .iter()
+            .map(|(entity, _, breath, .., player)| (entity, breath.clone(), *player))
+            .collect();
+        for (entity, breath, player) in &breaths {
+            let Some((_, opponent, _)) = breaths.iter().find(|(other, ..)| other != entity) else {
+                continue;
+            };
+            let was_desperate = desperate.contains(*entity);
+            if Desperation::is_earned(breath, opponent) {
+                commands.entity(*entity).insert((Desperation, FinalStand));
+                if !was_desperate {
+                    info!("Player {:?} enters DESPERATION! Final Stand ready", player);
+                }
+            } else if was_desperate {
+                commands.entity(*entity).remove::<Desperation>();
+                info!("Player {:?} took a Breath back - Desperation ends", player);
+            }
+        }
+
         // End current round and prepare for next
         match_state.end_round();
         info!("Round {} complete. Next round countdown starting...", match_state.round_number - 1);
@@ -257,3 +253,60 @@ pub fn debug_breath_display(
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::breath::MatchState;
+    use crate::components::character::Player;
+    use crate::components::initiative::Initiative;
+    use crate::components::state::CharacterState;
+    use crate::systems::momentum::Momentum;
+    use crate::systems::pressure::Pressure;
+
+    const GAME_CONFIG_FILE: &str = include_str!("../../assets/data/game_config.ron");
+
+    #[test]
+    fn test_final_stand_every_desperate_round() {
+        let mut world = World::new();
+        world.insert_resource(GameConfig::parse(GAME_CONFIG_FILE).unwrap());
+        world.init_resource::<MatchState>();
+        world.init_resource::<Events<RoundEndEvent>>();
+
+        // Five Breaths each, so losing rounds keeps a player desperate
+        let fighter = |player| {
+            (
+                player,
+                Health::new(100.0),
+                Breath::new(5),
+                Transform::default(),
+                CharacterState::Idle,
+                Initiative::default(),
+                Pressure::default(),
+                Momentum::default(),
+            )
+        };
+        let one = world.spawn(fighter(Player::One)).id();
+        let two = world.spawn(fighter(Player::Two)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(handle_round_end);
+        let mut lose_round = |world: &mut World| {
+            world.send_event(RoundEndEvent { winner: two, reason: RoundEndReason::Timeout });
+            schedule.run(world);
+        };
+
+        lose_round(&mut world);
+        assert!(!world.entity(one).contains::<Desperation>());
+        lose_round(&mut world);
+        assert!(world.entity(one).contains::<Desperation>());
+        assert!(world.entity(one).contains::<FinalStand>());
+
+        // Final Stand spent this round; still desperate into the next one
+        world.entity_mut(one).remove::<FinalStand>();
+        lose_round(&mut world);
+        assert!(world.entity(one).contains::<Desperation>());
+        assert!(world.entity(one).contains::<FinalStand>());
+        assert!(!world.entity(two).contains::<FinalStand>());
+    }
+}
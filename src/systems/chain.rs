@@ -1,7 +1,8 @@
 use bevy::prelude::*;
-use crate::components::character::Player;
+use serde::{Deserialize, Serialize};
+use crate::components::character::{Facing, Player};
 use crate::components::combo::InputBuffer;
-use crate::components::movelist::AttackDirection;
+use crate::components::movelist::Movelist;
 use crate::components::state::{AttackPhase, AttackType, CharacterState, StateTimer};
 use crate::events::combat_events::HitEvent;
 use crate::systems::input::CurrentInputs;
@@ -54,7 +55,7 @@ pub fn age_input_buffers(
 // ==================== CHAIN STATE ====================
 
 /// Component to track chain attack state
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct ChainState {
     /// Number of attacks in current chain (0-2)
     pub chain_count: u8,
@@ -114,8 +115,8 @@ pub fn mark_chainable_on_hit(
     mut query: Query<(&mut ChainState, &CharacterState, &crate::components::movelist::Movelist)>,
 ) {
     for event in hit_events.read() {
-        // Only unblocked hits can be chained
-        if event.was_blocked {
+        // Only clean hits can be chained (not blocked, absorbed or parried)
+        if event.was_blocked || event.absorbed || event.parried {
             continue;
         }
 
@@ -231,15 +232,15 @@ pub fn handle_chain_input(
     mut query: Query<(
         Entity,
         &Player,
-        &Transform,
+        &Facing,
         &mut CharacterState,
         &mut ChainState,
         &InputBuffer,
-        Option<&mut StateTimer>
+        Option<&mut StateTimer>,
+        &Movelist,
     )>,
-    all_chars_query: Query<(&Transform, &Player), With<crate::components::character::Character>>,
 ) {
-    for (entity, player, transform, mut state, mut chain_state, buffer, timer) in query.iter_mut() {
+    for (entity, player, facing, mut state, mut chain_state, buffer, timer, movelist) in query.iter_mut() {
         // Skip if no timer (shouldn't happen during chain windows, but be safe)
         let Some(mut timer) = timer else {
             continue;
@@ -275,20 +276,19 @@ pub fn handle_chain_input(
                 Player::Two => &inputs.player_two,
             };
 
-            // Get opponent position to determine facing direction
-            let opponent_x = all_chars_query.iter()
-                .find(|(_, p)| **p != *player)  // Find the other player
-                .map(|(t, _)| t.translation.x)
-                .unwrap_or(0.0);
-            let player_x = transform.translation.x;
-
-            // Determine attack direction from current input
-            let direction = input.get_attack_direction(*player, opponent_x, player_x);
+            // Determine attack direction from current input, relative to facing
+            let direction = input.get_attack_direction(*facing);
+            let Some(move_id) = movelist.resolve(attack_type, direction) else {
+                continue;
+            };
+            let Some(move_data) = movelist.get(move_id) else {
+                continue;
+            };
 
             // CHAIN CANCEL!
             info!(
                 "Player {:?} CANCEL → {:?} {:?} (chain: {}, hit: {})",
-                player, direction, attack_type, chain_state.chain_count, chain_state.hit_count
+                player, move_id.direction, attack_type, chain_state.chain_count, chain_state.hit_count
             );
 
             // Update chain state
@@ -300,20 +300,15 @@ pub fn handle_chain_input(
             // Transition to new attack with direction
             *state = CharacterState::Attacking {
                 attack_type,
-                direction,  // Use player's current directional input!
+                direction: move_id.direction,  // Use player's current directional input!
                 phase: AttackPhase::Startup,
             };
 
-            // Reset timer with appropriate startup frames
-            let startup = match attack_type {
-                AttackType::Light => 5,
-                AttackType::Heavy => 11,
-                AttackType::Grab => 10,
-            };
-            timer.reset(startup);
+            // Reset timer with the new move's startup frames
+            timer.reset(move_data.startup_frames);
 
             // Spawn hitbox for new attack
-            let hitbox = crate::systems::attack::create_hitbox(attack_type);
+            let hitbox = crate::systems::attack::create_hitbox(move_data);
             if let Some(mut entity_commands) = commands.get_entity(entity) {
                 entity_commands.insert(hitbox);
             }
@@ -1,26 +1,67 @@
 use bevy::prelude::*;
-use crate::components::character::Player;
+use crate::components::character::{Facing, Player};
 use crate::components::combat::{Hitbox, Hurtbox};
-use crate::components::state::{AttackPhase, CharacterState};
+use crate::components::guard::PunishWindow;
+use crate::components::movelist::Movelist;
+use crate::components::state::{AttackPhase, AttackType, CharacterState, StateTimer};
 use crate::events::combat_events::HitEvent;
 use crate::systems::evade::EvadeData;
+use crate::systems::input::{CurrentInputs, PlayerInput};
+use crate::systems::jump::JumpArc;
+use crate::systems::tenuki::{self, Tenuki};
+
+/// Extra hitstun on a counter hit
+const COUNTER_HIT_STUN_BONUS: u32 = 10;
 
 /// Detect collisions between active hitboxes and hurtboxes
 /// Only checks between different players (can't hit yourself)
+///
+/// The attacker's move is resolved here, once; the `HitEvent` carries its
+/// damage, chip, guard damage and hitstop to the systems reacting to it.
+///
+/// Blocking guards the way the defender faces. A hit from behind (a
+/// cross-up: the attacker passed the defender, who kept facing the old side)
+/// is only blocked while the defender also holds away from the attacker.
+/// A stance with `guards` blocks the same way.
+///
+/// Hitstun and blockstun come from how much of the move the attacker has
+/// left and its `on_hit` / `on_block` (see `MoveData::stun_frames`); a
+/// defender in blockstun keeps blocking.
+///
+/// Each attack connects once. A Light that meets an armored Heavy in startup
+/// is absorbed instead of counter hitting (see `tenuki`).
+///
+/// A ground attack catching a jumping fighter is an anti-air and counts as a
+/// counter hit; grabs don't reach fighters in the air.
+///
+/// Uses `Transform` rather than `GlobalTransform`: the latter is only
+/// propagated once per rendered frame and would lag the simulation tick.
+#[allow(clippy::type_complexity)]
 pub fn detect_hits(
-    hitbox_query: Query<(Entity, &Hitbox, &GlobalTransform, &Player, &CharacterState)>,
-    hurtbox_query: Query<(Entity, &Hurtbox, &GlobalTransform, &Player, &CharacterState, Option<&EvadeData>)>,
+    mut hitbox_query: Query<(Entity, &mut Hitbox, &Transform, &Player, &CharacterState, &Movelist, Option<&StateTimer>)>,
+    hurtbox_query: Query<(Entity, &Hurtbox, &Transform, &Player, &CharacterState, &Facing, Option<&EvadeData>, Has<PunishWindow>)>,
+    armor_query: Query<(&Movelist, Option<&Tenuki>)>,
+    airborne_query: Query<(), With<JumpArc>>,
+    inputs: Res<CurrentInputs>,
     mut hit_events: EventWriter<HitEvent>,
 ) {
-    for (attacker_entity, hitbox, attacker_transform, attacker_player, attacker_state) in hitbox_query.iter() {
-        // Skip if hitbox is not active
-        if !hitbox.active {
+    for (attacker_entity, mut hitbox, attacker_transform, attacker_player, attacker_state, movelist, attacker_timer) in hitbox_query.iter_mut() {
+        // Skip if hitbox is not active, or this attack already hit
+        if !hitbox.active || hitbox.connected {
             continue;
         }
 
-        let hitbox_rect = hitbox.world_rect(&attacker_transform.compute_transform());
+        // Move being performed (hitboxes are only active while attacking)
+        let Some((move_id, move_data)) = attacker_state
+            .move_id()
+            .and_then(|move_id| movelist.get(move_id).map(|data| (move_id, data)))
+        else {
+            continue;
+        };
+
+        let hitbox_rects = hitbox.world_rects(attacker_transform);
 
-        for (defender_entity, hurtbox, defender_transform, defender_player, defender_state, evade_data) in hurtbox_query.iter() {
+        for (defender_entity, hurtbox, defender_transform, defender_player, defender_state, defender_facing, evade_data, punishable) in hurtbox_query.iter() {
             // Can't hit yourself
             if attacker_player == defender_player {
                 continue;
@@ -34,56 +75,103 @@ pub fn detect_hits(
                 }
             }
 
-            let hurtbox_rect = hurtbox.world_rect(&defender_transform.compute_transform());
+            // Grabs only catch fighters on the ground
+            let defender_airborne = airborne_query.contains(defender_entity);
+            if defender_airborne && move_id.attack_type == AttackType::Grab {
+                continue;
+            }
+
+            let hurtbox_rect = hurtbox.world_rect(defender_transform);
 
             // AABB collision detection
-            if rects_intersect(&hitbox_rect, &hurtbox_rect) {
+            if hitbox_rects.iter().any(|hitbox_rect| rects_intersect(hitbox_rect, &hurtbox_rect)) {
                 // Hit detected!
-                // Get attack type from attacker's state
-                let attack_type = if let CharacterState::Attacking { attack_type, .. } = attacker_state {
-                    *attack_type
-                } else {
-                    // Fallback to Light if not in attacking state (shouldn't happen)
-                    crate::components::state::AttackType::Light
-                };
+                let mut event = HitEvent::new(attacker_entity, defender_entity, move_id, move_data);
 
-                let mut event = HitEvent::new(attacker_entity, defender_entity, hitbox.damage, attack_type);
+                // Check if defender's Heavy absorbs this Light - TENUKI!
+                let is_absorbed = armor_query
+                    .get(defender_entity)
+                    .is_ok_and(|(defender_movelist, spent)| {
+                        tenuki::can_absorb(defender_state, defender_movelist, spent.is_some(), move_id.attack_type)
+                    });
 
-                // Check if defender is in startup (vulnerable) - COUNTER HIT!
-                let is_counter_hit = matches!(
+                // Check if defender is in startup (vulnerable), was Perfect parried
+                // or was caught jumping by a ground attack - COUNTER HIT!
+                let is_anti_air = defender_airborne && !airborne_query.contains(attacker_entity);
+                let is_counter_hit = punishable || is_anti_air || matches!(
                     defender_state,
                     CharacterState::Attacking { phase: AttackPhase::Startup, .. }
                 );
 
-                // Check if defender is blocking
-                let is_blocking = matches!(defender_state, CharacterState::Blocking);
+                // Check if defender is blocking, and facing the hit or holding away from a cross-up
+                let defender_input = match defender_player {
+                    Player::One => &inputs.player_one,
+                    Player::Two => &inputs.player_two,
+                };
+                let is_guarding_stance = match defender_state {
+                    CharacterState::Stance { button } => armor_query
+                        .get(defender_entity)
+                        .is_ok_and(|(defender_movelist, _)| defender_movelist.stance(*button).is_some_and(|stance| stance.guards)),
+                    _ => false,
+                };
+                let is_blocking = (matches!(defender_state, CharacterState::Blocking | CharacterState::Blockstun { .. }) || is_guarding_stance)
+                    && guards_against(*defender_facing, defender_transform.translation.x, attacker_transform.translation.x, defender_input);
 
                 // Check for unblockable property
                 let is_unblockable = hitbox.properties.iter().any(|p| matches!(p, crate::components::combat::AttackProperty::Unblockable));
 
-                if is_counter_hit {
-                    event = event.counter_hit();
-                }
+                if is_absorbed {
+                    // Taken on the body: neither a counter hit nor blocked
+                    event = event.absorbed();
+                } else if matches!(defender_state, CharacterState::Parrying { .. }) {
+                    // Deflected: graded and rewarded by `guard::check_parry_success`
+                    event = event.parried();
+                } else {
+                    if is_counter_hit {
+                        event = event.counter_hit();
+                    }
 
-                if is_unblockable {
-                    event = event.unblockable();
-                } else if is_blocking {
-                    event = event.blocked();
+                    if is_unblockable {
+                        event = event.unblockable();
+                    } else if is_blocking {
+                        event = event.blocked();
+                    }
                 }
 
                 let was_blocked = event.was_blocked;
                 let is_counter = event.counter_hit;
+                let was_parried = event.parried;
+                event.hitstop = move_data.get_hitstop(was_blocked, is_counter);
+
+                let active_elapsed = attacker_timer.map_or(0, |timer| timer.elapsed);
+                let mut stun_frames = move_data.stun_frames(active_elapsed, was_blocked);
+                if is_counter {
+                    stun_frames += COUNTER_HIT_STUN_BONUS;
+                }
+                event = event.with_stun(stun_frames, move_data.frames_left_after(active_elapsed));
                 hit_events.send(event);
+                hitbox.connected = true;
 
                 debug!(
-                    "Hit detected! {:?} hit {:?} for {} damage (blocked: {}, counter: {})",
-                    attacker_player, defender_player, hitbox.damage, was_blocked, is_counter
+                    "Hit detected! {:?} hit {:?} with {} (blocked: {}, counter: {}, parried: {})",
+                    attacker_player, defender_player, move_data.name, was_blocked, is_counter, was_parried
                 );
+                break;
             }
         }
     }
 }
 
+/// Whether a block faces the attacker, or covers a cross-up by holding away
+fn guards_against(facing: Facing, defender_x: f32, attacker_x: f32, input: &PlayerInput) -> bool {
+    let towards_attacker = Facing::towards(defender_x, attacker_x);
+    if towards_attacker == facing || attacker_x == defender_x {
+        return true;
+    }
+    // Cross-up: hold away from where the attacker now is
+    input.movement.x * towards_attacker.sign() < -0.5
+}
+
 /// AABB (Axis-Aligned Bounding Box) collision detection
 fn rects_intersect(a: &Rect, b: &Rect) -> bool {
     a.min.x < b.max.x && a.max.x > b.min.x && a.min.y < b.max.y && a.max.y > b.min.y
@@ -98,16 +186,17 @@ pub fn debug_draw_boxes(
     // Draw active hitboxes in red
     for (hitbox, transform) in hitbox_query.iter() {
         if hitbox.active {
-            let rect = hitbox.world_rect(&transform.compute_transform());
-            let center = (rect.min + rect.max) / 2.0;
-            let size = rect.max - rect.min;
-
-            gizmos.rect_2d(
-                center,
-                0.0,
-                size,
-                Color::srgb(1.0, 0.0, 0.0), // Red for active hitboxes
-            );
+            for rect in hitbox.world_rects(&transform.compute_transform()) {
+                let center = (rect.min + rect.max) / 2.0;
+                let size = rect.max - rect.min;
+
+                gizmos.rect_2d(
+                    center,
+                    0.0,
+                    size,
+                    Color::srgb(1.0, 0.0, 0.0), // Red for active hitboxes
+                );
+            }
         }
     }
 
@@ -129,6 +218,148 @@ pub fn debug_draw_boxes(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::components::initiative::Initiative;
+    use crate::components::movelist::AttackDirection;
+    use crate::data::character::CharacterData;
+    use crate::systems::{attack, guard, initiative};
+
+    const DEFAULT_CHARACTER_FILE: &str = include_str!("../../assets/data/characters/default.character.ron");
+
+    /// Attack, hit and stun systems in simulation order
+    fn combat_world() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<CurrentInputs>();
+        world.init_resource::<Events<HitEvent>>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                attack::progress_attack_phases,
+                guard::progress_stun,
+                attack::update_attack_boxes,
+                detect_hits,
+                guard::apply_blockstun,
+                initiative::apply_frame_advantage,
+            )
+                .chain(),
+        );
+        (world, schedule)
+    }
+
+    fn spawn_fighter(world: &mut World, player: Player, x: f32, facing: Facing, movelist: &Movelist) -> Entity {
+        world
+            .spawn((
+                player,
+                facing,
+                CharacterState::Idle,
+                Transform::from_xyz(x, 0.0, 0.0),
+                Hurtbox::default(),
+                movelist.clone(),
+                Initiative::default(),
+            ))
+            .id()
+    }
+
+    /// Start a move the way `handle_attack_input` does
+    fn start_attack(world: &mut World, entity: Entity, attack_type: AttackType, movelist: &Movelist) {
+        let move_data = movelist.get_move(attack_type, AttackDirection::Neutral).unwrap();
+        world.entity_mut(entity).insert((
+            CharacterState::Attacking {
+                attack_type,
+                direction: AttackDirection::Neutral,
+                phase: AttackPhase::Startup,
+            },
+            StateTimer::new(move_data.startup_frames),
+            attack::create_hitbox(move_data),
+        ));
+    }
+
+    #[test]
+    fn test_blocked_heavy_punishable_by_jab() {
+        let movelist = CharacterData::parse(DEFAULT_CHARACTER_FILE).unwrap().movelist();
+        let heavy = movelist.get_move(AttackType::Heavy, AttackDirection::Neutral).unwrap().clone();
+
+        // Blocked on any active frame, the defender is free `on_block` frames first
+        for connect_frame in 0..heavy.active_frames {
+            let (mut world, mut schedule) = combat_world();
+            world.resource_mut::<CurrentInputs>().player_two.block = true;
+            let attacker = spawn_fighter(&mut world, Player::One, 0.0, Facing::Right, &movelist);
+            // Out of reach until the chosen active frame
+            let defender = spawn_fighter(&mut world, Player::Two, 1000.0, Facing::Left, &movelist);
+            world.entity_mut(defender).insert(CharacterState::Blocking);
+            start_attack(&mut world, attacker, AttackType::Heavy, &movelist);
+
+            let mut defender_free = None;
+            let mut attacker_idle = None;
+            let connect_tick = heavy.startup_frames + connect_frame;
+            for tick in 1..=100u32 {
+                if tick == connect_tick {
+                    world.entity_mut(defender).get_mut::<Transform>().unwrap().translation.x = 150.0;
+                }
+                schedule.run(&mut world);
+
+                if tick == connect_tick {
+                    assert!(matches!(world.get::<CharacterState>(defender), Some(CharacterState::Blockstun { .. })));
+                    assert_eq!(world.get::<Initiative>(attacker).unwrap().frames, heavy.on_block);
+                }
+                if tick > connect_tick && defender_free.is_none() && matches!(world.get::<CharacterState>(defender), Some(CharacterState::Blocking)) {
+                    defender_free = Some(tick);
+                }
+                if attacker_idle.is_none() && matches!(world.get::<CharacterState>(attacker), Some(CharacterState::Idle)) {
+                    attacker_idle = Some(tick);
+                }
+            }
+            let (defender_free, attacker_idle) = (defender_free.unwrap(), attacker_idle.unwrap());
+            assert_eq!(defender_free as i32 - attacker_idle as i32, heavy.on_block, "connecting on active frame {}", connect_frame);
+        }
+
+        // A jab pressed on the first free frame lands before the Heavy recovers
+        let (mut world, mut schedule) = combat_world();
+        world.resource_mut::<CurrentInputs>().player_two.block = true;
+        let attacker = spawn_fighter(&mut world, Player::One, 0.0, Facing::Right, &movelist);
+        let defender = spawn_fighter(&mut world, Player::Two, 150.0, Facing::Left, &movelist);
+        world.entity_mut(defender).insert(CharacterState::Blocking);
+        start_attack(&mut world, attacker, AttackType::Heavy, &movelist);
+
+        let mut punished = false;
+        let mut jabbing = false;
+        for _ in 0..100 {
+            // Out of blockstun, still blocking
+            if !jabbing
+                && world.get::<Initiative>(attacker).unwrap().frames < 0
+                && matches!(world.get::<CharacterState>(defender), Some(CharacterState::Blocking))
+            {
+                start_attack(&mut world, defender, AttackType::Light, &movelist);
+                jabbing = true;
+            }
+            schedule.run(&mut world);
+
+            let events = world.resource::<Events<HitEvent>>();
+            if events.get_reader().read(events).any(|hit| hit.attacker == defender) {
+                assert!(matches!(
+                    world.get::<CharacterState>(attacker),
+                    Some(CharacterState::Attacking { phase: AttackPhase::Recovery, .. })
+                ));
+                punished = true;
+                break;
+            }
+        }
+        assert!(punished);
+    }
+
+    #[test]
+    fn test_cross_up_needs_holding_away() {
+        let neutral = PlayerInput::default();
+        let hold_left = PlayerInput { movement: Vec2::new(-1.0, 0.0), ..default() };
+        let hold_right = PlayerInput { movement: Vec2::new(1.0, 0.0), ..default() };
+
+        // Facing right, attacked from the front: the block holds
+        assert!(guards_against(Facing::Right, 0.0, 100.0, &neutral));
+        // Attacker crossed to the left: only holding right (away) blocks
+        assert!(!guards_against(Facing::Right, 0.0, -100.0, &neutral));
+        assert!(!guards_against(Facing::Right, 0.0, -100.0, &hold_left));
+        assert!(guards_against(Facing::Right, 0.0, -100.0, &hold_right));
+    }
 
     #[test]
     fn test_rects_intersect() {
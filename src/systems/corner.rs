@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::character::{Character, Corner};
+use crate::data::game_config::GameConfig;
+use crate::events::combat_events::HitEvent;
+use crate::systems::jump::JumpArc;
+
+/// Sliding away from a hit or block, a few pixels per frame
+///
+/// On the defender, or on the attacker when the defender is cornered and
+/// has nowhere to slide (see `push_back_on_hit`).
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct Pushback {
+    /// -1.0 left or 1.0 right
+    pub direction: f32,
+    /// Pixels still to slide
+    pub remaining: f32,
+}
+
+/// Work out each fighter's corner state from its position
+///
+/// Runs after stage clamping and body collision, so everything later in the
+/// tick (and the next tick's input) sees where the fighter ended up.
+pub fn update_corners(
+    config: Res<GameConfig>,
+    mut query: Query<(&Transform, &mut Corner), With<Character>>,
+) {
+    let limit = config.stage_limit();
+    for (transform, mut corner) in query.iter_mut() {
+        let now = Corner::at(transform.translation.x, limit, config.corner_depth);
+        if *corner != now {
+            *corner = now;
+            debug!("Corner state now {:?} at x={:.1}", now, transform.translation.x);
+        }
+    }
+}
+
+/// Hits and blocks push the defender away from the attacker; a cornered
+/// defender can't give ground, so the attacker is pushed out instead
+///
+/// Parried and absorbed hits, and hits on jumping fighters, push nobody.
+pub fn push_back_on_hit(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut hit_events: EventReader<HitEvent>,
+    query: Query<(&Transform, &Corner, Has<JumpArc>)>,
+) {
+    for event in hit_events.read() {
+        if event.parried || event.absorbed {
+            continue;
+        }
+        let (Ok((attacker, _, _)), Ok((defender, corner, airborne))) = (query.get(event.attacker), query.get(event.defender)) else {
+            continue;
+        };
+        if airborne {
+            continue;
+        }
+
+        let distance = if event.was_blocked { config.block_pushback } else { config.hit_pushback };
+        let away = if defender.translation.x >= attacker.translation.x { 1.0 } else { -1.0 };
+
+        if corner.into_wall(away) {
+            commands.entity(event.attacker).insert(Pushback {
+                direction: -away,
+                remaining: distance,
+            });
+            debug!("Pushback of {} moved onto the attacker {:?}", distance, event.attacker);
+        } else {
+            commands.entity(event.defender).insert(Pushback {
+                direction: away,
+                remaining: distance,
+            });
+        }
+    }
+}
+
+/// Slide fighters along their pushback (clamped to the stage afterwards)
+pub fn apply_pushback(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut query: Query<(Entity, &mut Transform, &mut Pushback)>,
+) {
+    for (entity, mut transform, mut pushback) in query.iter_mut() {
+        let step = pushback.remaining.min(config.pushback_speed);
+        transform.translation.x += step * pushback.direction;
+        pushback.remaining -= step;
+
+        if pushback.remaining <= 0.0 {
+            commands.entity(entity).remove::<Pushback>();
+        }
+    }
+}
+
+/// Bright edge on the wall behind a cornered fighter
+pub fn visualize_corner(
+    mut gizmos: Gizmos,
+    config: Res<GameConfig>,
+    query: Query<&Corner>,
+) {
+    let edge = config.stage_width / 2.0;
+    for corner in query.iter() {
+        if !corner.is_cornered() {
+            continue;
+        }
+        let x = edge * corner.wall_sign();
+        gizmos.line_2d(
+            Vec2::new(x, -config.stage_height / 2.0),
+            Vec2::new(x, config.stage_height / 2.0),
+            Color::srgba(1.0, 0.45, 0.2, 0.8),
+        );
+    }
+}
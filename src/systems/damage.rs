@@ -1,44 +1,48 @@
 use bevy::prelude::*;
 use crate::components::character::Player;
-use crate::components::state::CharacterState;
+use crate::components::state::{AttackPhase, AttackType, CharacterState, StaggerSource};
+use crate::data::game_config::GameConfig;
 use crate::events::combat_events::HitEvent;
 
 /// Apply hit reactions when attacks connect
+///
+/// Hits put the defender in hitstun for the event's `stun_frames`. A grab
+/// staggers instead, for at least the medium tier, and a hit on an attack in
+/// recovery (a whiff punish) for at least the light tier.
 pub fn apply_hit_reactions(
+    config: Res<GameConfig>,
     mut hit_events: EventReader<HitEvent>,
-    mut query: Query<(&mut CharacterState, &mut Sprite, &Player)>,
+    mut query: Query<(&mut CharacterState, &Player)>,
 ) {
     for event in hit_events.read() {
-        // Skip if the hit was blocked (guard system handles that)
-        if event.was_blocked {
+        // Skip if the hit was blocked (guard system handles that), absorbed or parried
+        if event.was_blocked || event.absorbed || event.parried {
             continue;
         }
 
         // Apply hitstun to defender
-        if let Ok((mut state, mut sprite, player)) = query.get_mut(event.defender) {
-            // Base hitstun
-            let base_hitstun = match event.damage {
-                1 => 15, // Light attack: 15 frames (~0.25 seconds)
-                2 => 25, // Heavy attack: 25 frames (~0.42 seconds)
-                _ => 20, // Default
-            };
-
-            // Counter hit bonus: +10 frames
-            let hitstun_frames = if event.counter_hit {
-                base_hitstun + 10
+        if let Ok((mut state, player)) = query.get_mut(event.defender) {
+            // Counter hits already carry their extra hitstun
+            let source = if event.move_id.attack_type == AttackType::Grab {
+                Some(StaggerSource::Grab)
+            } else if matches!(*state, CharacterState::Attacking { phase: AttackPhase::Recovery, .. }) {
+                Some(StaggerSource::WhiffPunish)
             } else {
-                base_hitstun
+                None
             };
 
-            *state = CharacterState::Staggered {
-                frames_remaining: hitstun_frames,
-            };
-
-            // Visual feedback: gold for counter hit, red for normal hit
-            sprite.color = if event.counter_hit {
-                Color::srgb(1.0, 0.85, 0.0) // Gold/yellow for counter hit
-            } else {
-                Color::srgb(1.0, 0.3, 0.3) // Red for normal hit
+            let hitstun_frames = match source {
+                Some(source) => {
+                    let frames = event.stun_frames.max(config.stagger_frames(source.severity()));
+                    *state = CharacterState::staggered(source, frames);
+                    frames
+                }
+                None => {
+                    *state = CharacterState::Hitstun {
+                        frames_remaining: event.stun_frames,
+                    };
+                    event.stun_frames
+                }
             };
 
             if event.counter_hit {
@@ -58,12 +62,21 @@ pub fn apply_hit_reactions(
 
 /// Visual feedback for successful hits (flash effect)
 pub fn hit_flash_feedback(
-    mut query: Query<(&CharacterState, &mut Sprite, &Player), Changed<CharacterState>>,
+    mut hit_events: EventReader<HitEvent>,
+    mut query: Query<&mut Sprite>,
 ) {
-    for (state, mut sprite, player) in query.iter_mut() {
-        // Flash red when entering hitstun
-        if matches!(state, CharacterState::Staggered { .. }) {
-            sprite.color = Color::srgb(1.0, 0.3, 0.3);
+    for event in hit_events.read() {
+        if event.was_blocked || event.parried {
+            continue;
+        }
+
+        // Gold for counter hit, red for normal hit
+        if let Ok(mut sprite) = query.get_mut(event.defender) {
+            sprite.color = if event.counter_hit {
+                Color::srgb(1.0, 0.85, 0.0) // Gold/yellow for counter hit
+            } else {
+                Color::srgb(1.0, 0.3, 0.3) // Red for normal hit
+            };
         }
     }
 }
@@ -0,0 +1,397 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::breath::{RoundEndEvent, RoundEndReason};
+use crate::components::character::{Character, Player};
+use crate::components::combo::InputBuffer;
+use crate::components::health::Health;
+use crate::components::initiative::Initiative;
+use crate::components::state::{AttackType, CharacterState, StaggerSource, StateTimer};
+use crate::data::game_config::GameConfig;
+use crate::systems::chain::ChainState;
+use crate::systems::evade::EvadeData;
+use crate::systems::input::CurrentInputs;
+
+/// Killed by a Decisive Blow - the screen holds before the round ends
+///
+/// On the fallen fighter; `tick_kill_hold` sends the `RoundEndEvent` once
+/// the hold runs out.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct Slain {
+    pub frames_remaining: u32,
+    /// Length of the hold, for the presentation
+    pub hold_frames: u32,
+}
+
+/// Sent when a Final Parry turns a Decisive Blow aside
+#[derive(Event, Debug, Clone)]
+pub struct FinalParryEvent {
+    /// Fighter who survived
+    pub defender: Entity,
+    /// Fighter whose Decisive Blow was parried
+    pub attacker: Entity,
+}
+
+/// Sent when a Decisive Blow cuts its target down
+///
+/// The presentation plays the cut from this (sound included, once the game
+/// has audio), so it fires once per kill rather than every held frame.
+#[derive(Event, Debug, Clone)]
+pub struct DecisiveCutEvent {
+    /// Fighter who was cut down
+    pub defender: Entity,
+    /// Fighter whose Decisive Blow landed
+    pub attacker: Entity,
+}
+
+/// Whether `attacker` can start a Decisive Blow on `defender`
+///
+/// The defender must be Broken and in a medium or heavy stagger (a grab or
+/// guard break, not hitstun, a Perfect parry or a light whiff punish), and
+/// the attacker healthy enough (Whole or Cut) to commit to the stroke.
+pub fn decisive_blow_available(attacker: &Health, defender: &Health, defender_state: &CharacterState) -> bool {
+    defender.is_broken()
+        && attacker.can_decisive_blow()
+        && defender_state
+            .stagger_severity()
+            .is_some_and(|severity| severity.opens_decisive_blow())
+}
+
+/// Heavy becomes the Decisive Blow when it's available; block feints it
+///
+/// Starts from neutral or as a cancel out of an open chain window (Light →
+/// Heavy → Decisive). Runs before `attack::handle_attack_input` and
+/// `chain::handle_chain_input`, so a started wind-up takes the Heavy input.
+/// Blocking during the wind-up cancels it back to neutral (the held block
+/// then becomes a parry or block as usual).
+pub fn handle_decisive_input(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    inputs: Res<CurrentInputs>,
+    mut query: Query<(Entity, &Player, &mut CharacterState, &Health, &mut ChainState, &InputBuffer)>,
+) {
+    let fighters: Vec<(Entity, Health, CharacterState)> = query
+        .iter()
+        .map(|(entity, _, state, health, ..)| (entity, health.clone(), state.clone()))
+        .collect();
+
+    for (entity, player, mut state, health, mut chain_state, buffer) in query.iter_mut() {
+        let input = match player {
+            Player::One => &inputs.player_one,
+            Player::Two => &inputs.player_two,
+        };
+
+        if let CharacterState::DecisiveBlow { frames_remaining } = *state {
+            if frames_remaining > 0 && input.block {
+                // Feint: pull the stroke, re-enter whenever it's still open
+                *state = CharacterState::Idle;
+                info!("Player {:?} feints the Decisive Blow", player);
+            }
+            continue;
+        }
+
+        let from_neutral = matches!(*state, CharacterState::Idle | CharacterState::Walking) && input.heavy_attack;
+        let from_chain = chain_state.can_cancel_into(AttackType::Heavy) && buffer.is_buffered(AttackType::Heavy);
+        if !from_neutral && !from_chain {
+            continue;
+        }
+
+        let Some((_, opponent_health, opponent_state)) = fighters.iter().find(|(other, ..)| *other != entity) else {
+            continue;
+        };
+        if !decisive_blow_available(health, opponent_health, opponent_state) {
+            continue;
+        }
+
+        *state = CharacterState::DecisiveBlow {
+            frames_remaining: config.decisive_windup_frames,
+        };
+        chain_state.reset();
+        commands.entity(entity)
+            .remove::<StateTimer>()
+            .remove::<crate::systems::movement::DashData>();
+        info!("Player {:?} winds up the DECISIVE BLOW", player);
+    }
+}
+
+/// Parrying into a Decisive Blow only gets the tight Final Parry window
+pub fn tighten_final_parry(
+    config: Res<GameConfig>,
+    mut query: Query<(Entity, &mut CharacterState)>,
+) {
+    let winding_up: Vec<Entity> = query
+        .iter()
+        .filter(|(_, state)| matches!(state, CharacterState::DecisiveBlow { frames_remaining } if *frames_remaining > 0))
+        .map(|(entity, _)| entity)
+        .collect();
+    if winding_up.is_empty() {
+        return;
+    }
+
+    for (entity, mut state) in query.iter_mut() {
+        if winding_up.contains(&entity) {
+            continue;
+        }
+        if let CharacterState::Parrying { frames_remaining, .. } = &mut *state {
+            if *frames_remaining > config.final_parry_frames {
+                *frames_remaining = config.final_parry_frames;
+            }
+        }
+    }
+}
+
+/// Count the wind-up down and resolve the stroke
+///
+/// A defender parrying when it lands survives: both fighters reset to
+/// neutral, health untouched. Otherwise a stroke within reach kills (the
+/// hold starts, see `Slain`), and one out of reach or into evade i-frames
+/// whiffs and leaves the attacker staggered.
+#[allow(clippy::type_complexity)]
+pub fn resolve_decisive_blows(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut final_parry_events: EventWriter<FinalParryEvent>,
+    mut cut_events: EventWriter<DecisiveCutEvent>,
+    mut query: Query<(Entity, &Player, &mut CharacterState, &mut Transform, &mut Initiative, Option<&EvadeData>)>,
+) {
+    let strikes: Vec<(Entity, f32)> = query
+        .iter_mut()
+        .filter_map(|(entity, _, mut state, transform, ..)| match &mut *state {
+            CharacterState::DecisiveBlow { frames_remaining } if *frames_remaining > 0 => {
+                *frames_remaining -= 1;
+                (*frames_remaining == 0).then_some((entity, transform.translation.x))
+            }
+            _ => None,
+        })
+        .collect();
+
+    for (attacker, attacker_x) in strikes {
+        let Some((defender, defender_state, defender_x, invincible)) = query
+            .iter()
+            .find(|(entity, ..)| *entity != attacker)
+            .map(|(entity, _, state, transform, _, evade)| {
+                (entity, state.clone(), transform.translation.x, evade.is_some_and(|e| e.invincible))
+            })
+        else {
+            continue;
+        };
+
+        if matches!(defender_state, CharacterState::Parrying { .. }) {
+            // FINAL PARRY - back to neutral, still Broken
+            for (entity, player, mut state, mut transform, mut initiative, _) in query.iter_mut() {
+                *state = CharacterState::Idle;
+                initiative.reset();
+                transform.translation.x = match player {
+                    Player::One => -config.spawn_offset,
+                    Player::Two => config.spawn_offset,
+                };
+                commands.entity(entity).remove::<StateTimer>();
+            }
+            final_parry_events.send(FinalParryEvent { defender, attacker });
+            info!("FINAL PARRY! {:?} survives the Decisive Blow", defender);
+        } else if (attacker_x - defender_x).abs() <= config.decisive_reach && !invincible {
+            // The cut lands - hold the moment, then end the round
+            if let Ok((_, _, mut state, ..)) = query.get_mut(defender) {
+                *state = CharacterState::staggered(StaggerSource::DecisiveBlow, config.kill_hold_frames + 1);
+            }
+            commands.entity(defender).remove::<StateTimer>().insert(Slain {
+                frames_remaining: config.kill_hold_frames,
+                hold_frames: config.kill_hold_frames,
+            });
+            cut_events.send(DecisiveCutEvent { defender, attacker });
+            info!("DECISIVE BLOW lands on {:?}", defender);
+        } else if let Ok((_, player, mut state, ..)) = query.get_mut(attacker) {
+            // Whiffed the killing stroke: wide open
+            let source = StaggerSource::DecisiveBlow;
+            *state = CharacterState::staggered(source, config.stagger_frames(source.severity()));
+            info!("Player {:?} whiffs the Decisive Blow", player);
+        }
+    }
+}
+
+/// Hold the kill, then end the round for the fighter still standing
+pub fn tick_kill_hold(
+    mut commands: Commands,
+    mut round_end_events: EventWriter<RoundEndEvent>,
+    mut slain_query: Query<(Entity, &mut Slain)>,
+    fighters: Query<Entity, With<Character>>,
+) {
+    for (entity, mut slain) in slain_query.iter_mut() {
+        if slain.frames_remaining > 0 {
+            slain.frames_remaining -= 1;
+            continue;
+        }
+
+        commands.entity(entity).remove::<Slain>();
+        if let Some(winner) = fighters.iter().find(|other| *other != entity) {
+            round_end_events.send(RoundEndEvent {
+                winner,
+                reason: RoundEndReason::DecisiveBlow,
+            });
+        }
+    }
+}
+
+/// Killing posture: a ring closing in on the fighter as the stroke nears
+pub fn visualize_decisive_windup(
+    mut gizmos: Gizmos,
+    config: Res<GameConfig>,
+    query: Query<(&Transform, &CharacterState)>,
+) {
+    for (transform, state) in query.iter() {
+        let CharacterState::DecisiveBlow { frames_remaining } = state else {
+            continue;
+        };
+        let pos = transform.translation.truncate();
+
+        if *frames_remaining == 0 {
+            // Follow-through held over the kill
+            gizmos.line_2d(pos + Vec2::new(-60.0, 90.0), pos + Vec2::new(60.0, -90.0), Color::srgb(0.9, 0.9, 0.9));
+            continue;
+        }
+
+        let progress = 1.0 - *frames_remaining as f32 / config.decisive_windup_frames.max(1) as f32;
+        let radius = 160.0 - progress * 100.0;
+        gizmos.circle_2d(pos, radius, Color::srgba(0.8, 0.0, 0.0, 0.3 + progress * 0.6));
+        gizmos.circle_2d(pos, 60.0, Color::srgba(1.0, 1.0, 1.0, 0.4));
+    }
+}
+
+/// Kill presentation: silence, ink spreading from the fallen fighter, then the cut
+///
+/// Only the drawing lives here; the cut's sound belongs on `DecisiveCutEvent`,
+/// which `debug_decisive_cut_events` logs until the game has audio.
+pub fn kill_visual(
+    mut gizmos: Gizmos,
+    query: Query<(&Transform, &Slain)>,
+) {
+    // Fixed splatter layout, so every kill reads the same
+    const INK: [(f32, f32, f32); 7] = [
+        (0.0, 0.0, 1.0),
+        (-55.0, 30.0, 0.6),
+        (48.0, -40.0, 0.7),
+        (70.0, 45.0, 0.4),
+        (-35.0, -70.0, 0.5),
+        (20.0, 85.0, 0.35),
+        (-80.0, -10.0, 0.45),
+    ];
+
+    for (transform, slain) in query.iter() {
+        let pos = transform.translation.truncate();
+        let progress = 1.0 - slain.frames_remaining as f32 / slain.hold_frames.max(1) as f32;
+
+        for (x, y, size) in INK {
+            let spread = Vec2::new(x, y) * progress;
+            gizmos.circle_2d(pos + spread, 40.0 * size * progress.max(0.1), Color::srgba(0.02, 0.02, 0.05, 0.9));
+        }
+
+        // The cut comes last
+        if slain.frames_remaining <= slain.hold_frames / 4 {
+            gizmos.line_2d(pos + Vec2::new(-400.0, 120.0), pos + Vec2::new(400.0, -120.0), Color::WHITE);
+        }
+    }
+}
+
+/// Debug: Log Final Parries with the players involved
+pub fn debug_final_parry_events(
+    mut final_parry_events: EventReader<FinalParryEvent>,
+    players: Query<&Player>,
+) {
+    for event in final_parry_events.read() {
+        debug!(
+            "FinalParryEvent: defender={:?}, attacker={:?}",
+            players.get(event.defender).ok(),
+            players.get(event.attacker).ok()
+        );
+    }
+}
+
+/// Debug: Log Decisive Blow kills with the players involved
+pub fn debug_decisive_cut_events(
+    mut cut_events: EventReader<DecisiveCutEvent>,
+    players: Query<&Player>,
+) {
+    for event in cut_events.read() {
+        debug!(
+            "DecisiveCutEvent: defender={:?}, attacker={:?}",
+            players.get(event.defender).ok(),
+            players.get(event.attacker).ok()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::state::StaggerSeverity;
+
+    #[test]
+    fn test_decisive_blow_needs_broken_and_staggered() {
+        let healthy = Health::new(100.0);
+        let mut broken = Health::new(100.0);
+        broken.take_damage(80.0);
+        let staggered = CharacterState::staggered(StaggerSource::Grab, 10);
+
+        assert!(decisive_blow_available(&healthy, &broken, &staggered));
+        assert!(decisive_blow_available(&healthy, &broken, &CharacterState::staggered(StaggerSource::GuardBreak, 10)));
+        assert!(!decisive_blow_available(&healthy, &broken, &CharacterState::staggered(StaggerSource::WhiffPunish, 10)));
+        assert!(!decisive_blow_available(&healthy, &broken, &CharacterState::Hitstun { frames_remaining: 10 }));
+        assert!(!decisive_blow_available(&healthy, &broken, &CharacterState::Idle));
+        assert!(!decisive_blow_available(&healthy, &healthy, &staggered));
+        assert!(!decisive_blow_available(&broken, &broken, &staggered));
+    }
+
+    #[test]
+    fn test_stagger_severity_gates_decisive_blow() {
+        let attacker = Health::new(100.0);
+        let healthy = Health::new(100.0);
+        let mut broken = Health::new(100.0);
+        broken.take_damage(80.0);
+
+        let sources = [
+            (StaggerSource::WhiffPunish, StaggerSeverity::Light, false),
+            (StaggerSource::Grab, StaggerSeverity::Medium, true),
+            (StaggerSource::Parry, StaggerSeverity::Light, false),
+            (StaggerSource::GuardBreak, StaggerSeverity::Heavy, true),
+        ];
+        for (source, severity, opens) in sources {
+            let state = CharacterState::staggered(source, 20);
+            assert_eq!(state.stagger_severity(), Some(severity));
+            assert_eq!(decisive_blow_available(&attacker, &broken, &state), opens, "{:?} stagger", source);
+            // Never on a defender who isn't Broken
+            assert!(!decisive_blow_available(&attacker, &healthy, &state), "{:?} stagger", source);
+        }
+    }
+
+    #[test]
+    fn test_cut_sends_one_event_per_kill() {
+        let mut world = World::new();
+        world.insert_resource(GameConfig::parse(include_str!("../../assets/data/game_config.ron")).unwrap());
+        world.init_resource::<Events<FinalParryEvent>>();
+        world.init_resource::<Events<DecisiveCutEvent>>();
+
+        let attacker = world
+            .spawn((Player::One, CharacterState::DecisiveBlow { frames_remaining: 1 }, Transform::default(), Initiative::default()))
+            .id();
+        let defender = world
+            .spawn((
+                Player::Two,
+                CharacterState::staggered(StaggerSource::Grab, 30),
+                Transform::from_xyz(40.0, 0.0, 0.0),
+                Initiative::default(),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_decisive_blows);
+        // The stroke lands, then the follow-through is held
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let events = world.resource::<Events<DecisiveCutEvent>>();
+        let mut reader = events.get_reader();
+        let cuts: Vec<_> = reader.read(events).collect();
+        assert_eq!(cuts.len(), 1);
+        assert_eq!((cuts[0].defender, cuts[0].attacker), (defender, attacker));
+        assert!(world.entity(defender).contains::<Slain>());
+    }
+}
@@ -1,22 +1,28 @@
 use bevy::prelude::*;
-use crate::components::character::{Player, Velocity};
+use serde::{Deserialize, Serialize};
+use crate::components::character::{Corner, Player, Velocity};
 use crate::components::state::{CharacterState, StateTimer};
+use crate::data::game_config::GameConfig;
 use crate::systems::input::CurrentInputs;
 
 /// Evade state component to track i-frame window
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct EvadeData {
     pub direction: Vec2,
     pub invincible: bool,
 }
 
 /// Handle evade input (Shift + direction)
+///
+/// Cornered, the only ways out are forward and up or down: an evade with
+/// any pull into the wall doesn't start.
 pub fn handle_evade_input(
     mut commands: Commands,
     inputs: Res<CurrentInputs>,
-    mut query: Query<(Entity, &Player, &mut CharacterState, &mut Velocity)>,
+    config: Res<GameConfig>,
+    mut query: Query<(Entity, &Player, &mut CharacterState, &mut Velocity, &Corner)>,
 ) {
-    for (entity, player, mut state, mut velocity) in query.iter_mut() {
+    for (entity, player, mut state, mut velocity, corner) in query.iter_mut() {
         // Can only evade from Idle or Walking states
         if !matches!(*state, CharacterState::Idle | CharacterState::Walking) {
             continue;
@@ -31,6 +37,9 @@ pub fn handle_evade_input(
         // Check for evade input (step = Shift + direction)
         if input.step && input.movement.length() > 0.1 {
             let direction = input.movement.normalize();
+            if corner.into_wall(direction.x) {
+                continue;
+            }
 
             // Enter evade state
             *state = CharacterState::Idle; // We'll use a marker component instead
@@ -41,11 +50,11 @@ pub fn handle_evade_input(
                     direction,
                     invincible: false, // Becomes true after startup
                 },
-                StateTimer::new(10), // Total evade duration: 3f startup + 4f active + 3f recovery
+                StateTimer::new(config.evade_duration), // Startup + i-frames + recovery
             ));
 
             // Apply evade movement (fast dash)
-            velocity.0 = direction * 300.0; // Fast movement
+            velocity.0 = direction * config.evade_speed; // Fast movement
 
             info!("Player {:?} evading in direction {:?}", player, direction);
         }
@@ -55,17 +64,14 @@ pub fn handle_evade_input(
 /// Progress evade state and manage i-frames
 pub fn progress_evade(
     mut commands: Commands,
+    config: Res<GameConfig>,
     mut query: Query<(Entity, &mut EvadeData, &mut StateTimer, &mut Velocity)>,
 ) {
     for (entity, mut evade, mut timer, mut velocity) in query.iter_mut() {
         timer.tick();
 
-        // I-frames: active from frame 3 to frame 7 (4 frames total)
-        if timer.elapsed >= 3 && timer.elapsed < 7 {
-            evade.invincible = true;
-        } else {
-            evade.invincible = false;
-        }
+        // I-frames: active from evade_invincible_from up to evade_invincible_until
+        evade.invincible = (config.evade_invincible_from..config.evade_invincible_until).contains(&timer.elapsed);
 
         // Full speed throughout - no deceleration
 
@@ -3,10 +3,19 @@ use bevy::prelude::*;
 /// Game states - controls which systems run
 #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub enum GameState {
+    /// Waiting for character files (see `systems::loading`)
     #[default]
+    Loading,
     CharacterSelect,
     InGame,
     Victory,
+    /// Watching a recorded match (`--replay <file>`)
+    Replay,
+}
+
+/// Run condition: a match is on screen, either live or replayed
+pub fn in_match(state: Res<State<GameState>>) -> bool {
+    matches!(state.get(), GameState::InGame | GameState::Replay)
 }
 
 /// Resource to track selected characters
@@ -1,17 +1,21 @@
 use bevy::prelude::*;
 use crate::components::character::Player;
-use crate::components::guard::GuardMeter;
-use crate::components::state::{CharacterState, StateTimer};
+use crate::components::guard::{GuardMeter, ParryGrade, PunishWindow};
+use crate::components::movelist::Movelist;
+use crate::components::health::FinalStand;
+use crate::components::state::{CharacterState, StaggerSource, StateTimer};
+use crate::data::game_config::GameConfig;
 use crate::events::combat_events::{GuardBreakEvent, HitEvent, ParryEvent};
 use crate::systems::input::CurrentInputs;
+use crate::systems::simulation::FRAME_SECONDS;
 
 /// Handle block/parry input - hold for block, tap for parry
 pub fn handle_block_input(
     mut commands: Commands,
     inputs: Res<CurrentInputs>,
-    mut query: Query<(Entity, &Player, &mut CharacterState)>,
+    mut query: Query<(Entity, &Player, &mut CharacterState, &Movelist)>,
 ) {
-    for (entity, player, mut state) in query.iter_mut() {
+    for (entity, player, mut state, movelist) in query.iter_mut() {
         // Get input for this player
         let input = match player {
             Player::One => &inputs.player_one,
@@ -22,8 +26,11 @@ pub fn handle_block_input(
             // Can only INITIATE block/parry from Idle or Walking states
             match *state {
                 CharacterState::Idle | CharacterState::Walking => {
-                    // Start parry attempt (2f startup, 6f active window)
-                    *state = CharacterState::Parrying { frames_remaining: 6 };
+                    // Start parry attempt (2f startup, window from the character)
+                    *state = CharacterState::Parrying {
+                        frames_remaining: movelist.parry().window_frames,
+                        elapsed: 0,
+                    };
                     commands.entity(entity).insert(StateTimer::new(2)); // 2f startup
                     info!("Player {:?} attempting parry", player);
                 }
@@ -51,44 +58,77 @@ pub fn handle_block_input(
     }
 }
 
+/// Blocked hits hold the defender in blockstun for the event's `stun_frames`
+///
+/// A guarding stance takes the hit in the stance instead.
+pub fn apply_blockstun(
+    mut hit_events: EventReader<HitEvent>,
+    mut query: Query<(&mut CharacterState, &Player)>,
+) {
+    for event in hit_events.read() {
+        if !event.was_blocked {
+            continue;
+        }
+        if let Ok((mut state, player)) = query.get_mut(event.defender) {
+            if matches!(*state, CharacterState::Stance { .. }) {
+                continue;
+            }
+            *state = CharacterState::Blockstun {
+                frames_remaining: event.stun_frames,
+            };
+            debug!("Player {:?} in blockstun for {} frames", player, event.stun_frames);
+        }
+    }
+}
+
 /// Fill guard meter when blocking attacks
+///
+/// Blocking from a guarding stance scales the guard damage by the stance's
+/// `guard_damage_multiplier`.
 pub fn fill_guard_on_block(
     mut hit_events: EventReader<HitEvent>,
-    mut guard_query: Query<(&mut GuardMeter, &CharacterState)>,
+    mut guard_query: Query<(&mut GuardMeter, &CharacterState, &Movelist)>,
 ) {
     for event in hit_events.read() {
-        // Check if defender is blocking
-        if let Ok((mut guard, state)) = guard_query.get_mut(event.defender) {
-            if *state == CharacterState::Blocking && !event.unblockable {
-                // Calculate guard damage based on attack damage
-                let guard_damage = match event.damage {
-                    1 => 0.15, // Light attack: +15% guard
-                    2 => 0.35, // Heavy attack: +35% guard
-                    _ => 0.10, // Default
-                };
+        if !event.was_blocked {
+            continue;
+        }
+
+        if let Ok((mut guard, state, movelist)) = guard_query.get_mut(event.defender) {
+            // Guard damage comes from the blocked move
+            let multiplier = match state {
+                CharacterState::Stance { button } => movelist
+                    .stance(*button)
+                    .map_or(1.0, |stance| stance.guard_damage_multiplier),
+                _ => 1.0,
+            };
+            let guard_damage = event.guard_damage * multiplier;
 
-                guard.fill(guard_damage);
+            guard.fill(guard_damage);
 
-                info!(
-                    "Guard meter filled by {:.0}% (now at {:.0}%)",
-                    guard_damage * 100.0,
-                    guard.current * 100.0
-                );
-            }
+            info!(
+                "Guard meter filled by {:.0}% (now at {:.0}%)",
+                guard_damage * 100.0,
+                guard.current * 100.0
+            );
         }
     }
 }
 
 /// Passively drain guard meter when not blocking
 pub fn drain_guard_meter(
-    time: Res<Time>,
+    config: Res<GameConfig>,
     mut query: Query<(&mut GuardMeter, &CharacterState)>,
 ) {
     for (mut guard, state) in query.iter_mut() {
-        // Only drain when not blocking and guard > 0
-        if *state != CharacterState::Blocking && guard.current > 0.0 {
-            // Drain 5% per second
-            let drain_rate = 0.05 * time.delta_seconds();
+        // Only drain when not blocking (or holding a stance) and guard > 0
+        let guarding = matches!(
+            state,
+            CharacterState::Blocking | CharacterState::Blockstun { .. } | CharacterState::Stance { .. }
+        );
+        if !guarding && guard.current > 0.0 {
+            // Drain guard_depletion_rate per second
+            let drain_rate = config.guard_depletion_rate * FRAME_SECONDS;
             guard.drain(drain_rate);
         }
     }
@@ -96,15 +136,15 @@ pub fn drain_guard_meter(
 
 /// Check for guard break and trigger stagger
 pub fn check_guard_break(
+    config: Res<GameConfig>,
     mut query: Query<(Entity, &mut GuardMeter, &mut CharacterState), Changed<GuardMeter>>,
     mut break_events: EventWriter<GuardBreakEvent>,
 ) {
     for (entity, mut guard, mut state) in query.iter_mut() {
         if guard.is_broken() {
-            // Guard broken! Enter stagger state
-            *state = CharacterState::Staggered {
-                frames_remaining: 40, // ~0.67 seconds at 60 FPS
-            };
+            // Guard broken! Enter a heavy stagger
+            let source = StaggerSource::GuardBreak;
+            *state = CharacterState::staggered(source, config.stagger_frames(source.severity()));
 
             guard.reset();
 
@@ -115,19 +155,34 @@ pub fn check_guard_break(
     }
 }
 
-/// Progress stagger state (count down frames)
-pub fn progress_stagger(
-    mut query: Query<&mut CharacterState>,
+/// Count down stagger, hitstun and blockstun
+///
+/// Each ends on the frame it reaches 0, so a fighter is free exactly
+/// `frames_remaining` frames after it was set; out of blockstun with block
+/// still held the fighter keeps blocking (without a fresh parry attempt).
+pub fn progress_stun(
+    inputs: Res<CurrentInputs>,
+    mut query: Query<(&mut CharacterState, &Player)>,
 ) {
-    for mut state in query.iter_mut() {
-        if let CharacterState::Staggered { frames_remaining } = &mut *state {
-            if *frames_remaining > 0 {
-                *frames_remaining -= 1;
-            } else {
-                // Stagger complete, return to idle
-                *state = CharacterState::Idle;
-                debug!("Stagger complete, returning to Idle");
+    for (mut state, player) in query.iter_mut() {
+        match &mut *state {
+            CharacterState::Staggered { frames_remaining, .. } | CharacterState::Hitstun { frames_remaining } => {
+                *frames_remaining = frames_remaining.saturating_sub(1);
+                if *frames_remaining == 0 {
+                    *state = CharacterState::Idle;
+                }
             }
+            CharacterState::Blockstun { frames_remaining } => {
+                *frames_remaining = frames_remaining.saturating_sub(1);
+                if *frames_remaining == 0 {
+                    let input = match player {
+                        Player::One => &inputs.player_one,
+                        Player::Two => &inputs.player_two,
+                    };
+                    *state = if input.block { CharacterState::Blocking } else { CharacterState::Idle };
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -139,10 +194,11 @@ pub fn progress_parry(
     mut query: Query<(Entity, &Player, &mut CharacterState, Option<&mut StateTimer>)>,
 ) {
     for (entity, player, mut state, timer) in query.iter_mut() {
-        if let CharacterState::Parrying { frames_remaining } = &mut *state {
+        if let CharacterState::Parrying { frames_remaining, elapsed } = &mut *state {
             // Tick down active window
             if *frames_remaining > 0 {
                 *frames_remaining -= 1;
+                *elapsed += 1;
             } else {
                 // Parry window expired - check if block is still held
                 let input = match player {
@@ -166,44 +222,86 @@ pub fn progress_parry(
     }
 }
 
-/// Check for successful parries when attacks hit during parry window
+/// Grade parried hits and hand out the rewards
+///
+/// A parry caught in the first `perfect_frames` of the window, or any parry
+/// under Final Stand, is Perfect: guard restored, and the attacker staggered
+/// inside a `PunishWindow` so the answer is a guaranteed counter hit. A Final
+/// Stand parry restores the guard fully, whatever the character's
+/// `guard_restore`. A Late parry only negates the hit. Damage is skipped
+/// upstream via `HitEvent::parried`.
 pub fn check_parry_success(
     mut hit_events: EventReader<HitEvent>,
-    mut query: Query<(&mut CharacterState, &mut GuardMeter, &Player)>,
+    mut query: Query<(&mut CharacterState, &mut GuardMeter, &Movelist, &Player, Has<FinalStand>)>,
     mut parry_events: EventWriter<ParryEvent>,
     mut commands: Commands,
 ) {
     for event in hit_events.read() {
-        if let Ok((mut state, mut guard, player)) = query.get_mut(event.defender) {
-            // Check if defender is in parry window
-            if matches!(*state, CharacterState::Parrying { .. }) {
-                // PARRY SUCCESS!
-                info!("PARRY! Player {:?} deflected attack", player);
-
-                // Send parry event
-                parry_events.send(ParryEvent {
-                    defender: event.defender,
-                    attacker: event.attacker,
-                });
+        if !event.parried {
+            continue;
+        }
+        let Ok((mut state, mut guard, movelist, player, final_stand)) = query.get_mut(event.defender) else {
+            continue;
+        };
+        let CharacterState::Parrying { elapsed, .. } = *state else {
+            continue;
+        };
+        let parry = movelist.parry().clone();
 
-                // Restore guard meter
-                guard.current = (guard.current - 0.25).max(0.0);
+        // Final Stand turns this parry Perfect, once
+        let grade = if final_stand {
+            commands.entity(event.defender).remove::<FinalStand>();
+            info!("PERFECT PARRY! Player {:?} Final Stand", player);
+            ParryGrade::Perfect
+        } else {
+            let grade = parry.grade(elapsed);
+            info!("{:?} PARRY! Player {:?} deflected attack ({}f in)", grade, player, elapsed);
+            grade
+        };
 
-                // Return to idle (can act immediately)
-                *state = CharacterState::Idle;
-                commands.entity(event.defender).remove::<StateTimer>();
+        parry_events.send(ParryEvent {
+            defender: event.defender,
+            attacker: event.attacker,
+            grade,
+            final_stand,
+        });
 
-                // Stagger the attacker (punish for being parried)
-                if let Ok((mut attacker_state, _, _)) = query.get_mut(event.attacker) {
-                    *attacker_state = CharacterState::Staggered {
-                        frames_remaining: 20, // Longer stagger than normal hit
-                    };
-                }
+        // Return to idle (can act immediately)
+        *state = CharacterState::Idle;
+        commands.entity(event.defender).remove::<StateTimer>();
+
+        if grade == ParryGrade::Perfect {
+            guard.current = if final_stand {
+                0.0
+            } else {
+                (guard.current - parry.guard_restore).max(0.0)
+            };
+
+            // Stagger the attacker inside a punish window
+            if let Ok((mut attacker_state, ..)) = query.get_mut(event.attacker) {
+                *attacker_state = CharacterState::staggered(StaggerSource::Parry, parry.punish_frames);
+                commands.entity(event.attacker).insert(PunishWindow {
+                    frames_remaining: parry.punish_frames,
+                });
             }
         }
     }
 }
 
+/// Close punish windows once they run out
+pub fn tick_punish_window(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut PunishWindow)>,
+) {
+    for (entity, mut window) in query.iter_mut() {
+        if window.frames_remaining > 0 {
+            window.frames_remaining -= 1;
+        } else {
+            commands.entity(entity).remove::<PunishWindow>();
+        }
+    }
+}
+
 /// Visual feedback for blocking/parrying (change color slightly)
 pub fn visualize_blocking(
     mut query: Query<(&CharacterState, &mut Sprite, &Player), Changed<CharacterState>>,
@@ -224,8 +322,12 @@ pub fn visualize_blocking(
                 // Bright cyan when parrying (high risk/reward)
                 sprite.color = Color::srgb(0.3, 1.0, 1.0);
             }
-            CharacterState::Staggered { .. } => {
-                // Gray when staggered
+            CharacterState::Blockstun { .. } => {
+                // Dimmer silver while held in block
+                sprite.color = Color::srgb(0.65, 0.65, 0.72);
+            }
+            CharacterState::Staggered { .. } | CharacterState::Hitstun { .. } => {
+                // Gray when staggered or in hitstun
                 sprite.color = Color::srgb(0.5, 0.5, 0.5);
             }
             _ => {
@@ -244,9 +346,12 @@ pub fn parry_flash_effect(
     mut query: Query<&mut Sprite>,
 ) {
     for event in parry_events.read() {
-        // Flash defender bright white
+        // Flash defender gold on a Perfect parry, pale white on a Late one
         if let Ok(mut sprite) = query.get_mut(event.defender) {
-            sprite.color = Color::srgb(1.0, 1.0, 1.0);
+            sprite.color = match event.grade {
+                ParryGrade::Perfect => Color::srgb(1.0, 0.85, 0.3),
+                ParryGrade::Late => Color::srgb(0.9, 0.9, 0.9),
+            };
         }
     }
 }
@@ -264,3 +369,71 @@ pub fn debug_guard_meter(
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::guard::{ParryData, FINAL_STAND_INITIATIVE_STEAL};
+    use crate::components::initiative::Initiative;
+    use crate::components::movelist::{AttackDirection, MoveId};
+    use crate::components::state::AttackType;
+    use crate::data::character::CharacterData;
+    use crate::systems::initiative;
+
+    const DEFAULT_CHARACTER_FILE: &str = include_str!("../../assets/data/characters/default.character.ron");
+
+    /// Parry a Heavy `elapsed` frames into the window; returns the parrier's
+    /// guard and initiative afterwards
+    fn parry_heavy(elapsed: u32, final_stand: bool) -> (f32, i32) {
+        let mut movelist = CharacterData::parse(DEFAULT_CHARACTER_FILE).unwrap().movelist();
+        // A character with a stingy parry
+        movelist.set_parry(ParryData {
+            guard_restore: 0.1,
+            initiative_steal: 5,
+            ..ParryData::default()
+        });
+
+        let mut world = World::new();
+        world.init_resource::<Events<HitEvent>>();
+        world.init_resource::<Events<ParryEvent>>();
+        let attacker = world
+            .spawn((Player::One, CharacterState::Idle, GuardMeter::new(), movelist.clone(), Initiative::default()))
+            .id();
+        let defender = world
+            .spawn((
+                Player::Two,
+                CharacterState::Parrying { frames_remaining: 6 - elapsed, elapsed },
+                GuardMeter { current: 0.8, max: 1.0 },
+                movelist.clone(),
+                Initiative::default(),
+            ))
+            .id();
+        if final_stand {
+            world.entity_mut(defender).insert(FinalStand);
+        }
+
+        let move_id = MoveId::new(AttackType::Heavy, AttackDirection::Neutral);
+        let hit = HitEvent::new(attacker, defender, move_id, movelist.get(move_id).unwrap()).parried();
+        world.send_event(hit);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((check_parry_success, initiative::apply_parry_advantage).chain());
+        schedule.run(&mut world);
+
+        assert!(matches!(world.get::<CharacterState>(attacker), Some(CharacterState::Staggered { .. })));
+        (world.get::<GuardMeter>(defender).unwrap().current, world.get::<Initiative>(defender).unwrap().frames)
+    }
+
+    #[test]
+    fn test_final_stand_parry_rewards_fully() {
+        // A Perfect parry earns the character's own rewards
+        let (guard, initiative) = parry_heavy(0, false);
+        assert!((guard - 0.7).abs() < 1e-6);
+        assert_eq!(initiative, 5);
+
+        // Final Stand, even timed late, restores the guard and steals the full initiative
+        let (guard, initiative) = parry_heavy(4, true);
+        assert_eq!(guard, 0.0);
+        assert_eq!(initiative, FINAL_STAND_INITIATIVE_STEAL);
+    }
+}
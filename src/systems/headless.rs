@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use crate::components::breath::{MatchState, RoundEndEvent, RoundEndReason};
+use crate::components::character::{Corner, Player};
+use crate::components::state::CharacterState;
+use crate::headless::controller::{Controller, FighterView};
+use crate::systems::input::LatchedInputs;
+use crate::systems::simulation::SimFrame;
+
+/// How one round of a headless match ended
+#[derive(Debug, Clone)]
+pub struct RoundResult {
+    pub winner: Player,
+    pub reason: RoundEndReason,
+    /// Simulation frame the round ended on
+    pub frame: u32,
+    /// Frames Player One and Player Two each spent cornered
+    pub cornered_frames: (u32, u32),
+}
+
+/// Controllers and round results of the headless match in progress
+#[derive(Resource)]
+pub struct HeadlessMatch {
+    pub player_one: Controller,
+    pub player_two: Controller,
+    pub rounds: Vec<RoundResult>,
+    /// Cornered frames of the round in progress, as in `RoundResult`
+    pub cornered_frames: (u32, u32),
+}
+
+/// Ask each controller for its input for the upcoming tick
+///
+/// Writes `LatchedInputs`, taking the keyboard's place in front of
+/// `input::update_inputs`.
+pub fn feed_controllers(
+    frame: Res<SimFrame>,
+    mut headless: ResMut<HeadlessMatch>,
+    mut latched: ResMut<LatchedInputs>,
+    fighters: Query<(&Player, &Transform, &CharacterState, &Corner)>,
+) {
+    let position = |player: Player| {
+        fighters
+            .iter()
+            .find(|(p, ..)| **p == player)
+            .map(|(_, transform, state, corner)| (transform.translation.x, state.clone(), corner.is_cornered()))
+    };
+    let (Some((p1_x, p1_state, p1_cornered)), Some((p2_x, p2_state, p2_cornered))) = (position(Player::One), position(Player::Two)) else {
+        return;
+    };
+
+    let headless = &mut *headless;
+    latched.player_one = headless.player_one.input(frame.0, &FighterView {
+        position: p1_x,
+        opponent_position: p2_x,
+        state: p1_state,
+        cornered: p1_cornered,
+        opponent_cornered: p2_cornered,
+    });
+    latched.player_two = headless.player_two.input(frame.0, &FighterView {
+        position: p2_x,
+        opponent_position: p1_x,
+        state: p2_state,
+        cornered: p2_cornered,
+        opponent_cornered: p1_cornered,
+    });
+}
+
+/// Count the frames each fighter spends cornered during a round
+pub fn count_cornered_frames(
+    match_state: Res<MatchState>,
+    mut headless: ResMut<HeadlessMatch>,
+    fighters: Query<(&Player, &Corner)>,
+) {
+    if !match_state.round_active {
+        return;
+    }
+    for (player, corner) in fighters.iter() {
+        if !corner.is_cornered() {
+            continue;
+        }
+        match player {
+            Player::One => headless.cornered_frames.0 += 1,
+            Player::Two => headless.cornered_frames.1 += 1,
+        }
+    }
+}
+
+/// Record the winner and reason of every round that ended this tick
+pub fn record_round_ends(
+    mut round_end_events: EventReader<RoundEndEvent>,
+    frame: Res<SimFrame>,
+    mut headless: ResMut<HeadlessMatch>,
+    players: Query<&Player>,
+) {
+    for event in round_end_events.read() {
+        if let Ok(winner) = players.get(event.winner) {
+            let cornered_frames = std::mem::take(&mut headless.cornered_frames);
+            headless.rounds.push(RoundResult {
+                winner: *winner,
+                reason: event.reason,
+                frame: frame.0,
+                cornered_frames,
+            });
+        }
+    }
+}
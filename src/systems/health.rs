@@ -1,48 +1,53 @@
 use bevy::prelude::*;
-use crate::components::health::Health;
-use crate::components::state::AttackType;
+use crate::components::breath::Desperation;
+use crate::components::health::{FinalStand, Health, HealthState};
+use crate::data::game_config::GameConfig;
 use crate::events::combat_events::HitEvent;
 use crate::systems::momentum::Momentum;
 
-/// Calculate base damage for an attack type
-fn calculate_base_damage(attack_type: AttackType) -> f32 {
-    match attack_type {
-        AttackType::Light => 8.0,
-        AttackType::Heavy => 15.0,
-        AttackType::Grab => 12.0,
-    }
-}
-
 /// Apply health damage from hit events
+///
+/// Base damage is the move's `damage`, or its `chip_damage` when blocked.
+/// Chip can't take a fighter into Broken, except one already Broken (no
+/// chip forgiveness). Entering Broken grants Final Stand.
 pub fn apply_health_damage(
+    mut commands: Commands,
     mut hit_events: EventReader<HitEvent>,
     mut defender_query: Query<&mut Health>,
-    attacker_query: Query<&Momentum>,
+    attacker_query: Query<(&Momentum, Option<&Desperation>)>,
 ) {
     for event in hit_events.read() {
-        // Get base damage from attack type
-        let base_damage = calculate_base_damage(event.attack_type);
+        // A parry negates the hit entirely, Late or Perfect
+        if event.parried {
+            continue;
+        }
+
+        // Get base damage from the move (chip damage if blocked)
+        let base_damage = if event.was_blocked { event.chip_damage } else { event.damage };
 
         // Apply counter hit bonus (50% extra damage)
         let counter_multiplier = if event.counter_hit { 1.5 } else { 1.0 };
 
-        // Apply momentum bonus from attacker
-        let momentum_multiplier = if let Ok(momentum) = attacker_query.get(event.attacker) {
-            momentum.damage_bonus()
-        } else {
-            1.0
+        // Apply momentum and Desperation bonuses from attacker
+        let (momentum_multiplier, desperation_multiplier) = match attacker_query.get(event.attacker) {
+            Ok((momentum, desperation)) => (
+                momentum.damage_bonus(),
+                if desperation.is_some() { Desperation::DAMAGE_MULTIPLIER } else { 1.0 },
+            ),
+            Err(_) => (1.0, 1.0),
         };
 
-        // Calculate chip damage if blocked (25% of full damage)
-        let block_multiplier = if event.was_blocked { 0.25 } else { 1.0 };
-
         // Final damage calculation
-        let final_damage = base_damage * counter_multiplier * momentum_multiplier * block_multiplier;
+        let final_damage = base_damage * counter_multiplier * momentum_multiplier * desperation_multiplier;
 
         // Apply damage to defender
         if let Ok(mut health) = defender_query.get_mut(event.defender) {
             let old_state = health.state;
-            health.take_damage(final_damage);
+            if event.was_blocked {
+                health.take_chip_damage(final_damage);
+            } else {
+                health.take_damage(final_damage);
+            }
 
             // Log damage and state changes
             if health.state != old_state {
@@ -50,6 +55,11 @@ pub fn apply_health_damage(
                     "Health state changed: {:?} -> {:?} (took {:.1} damage)",
                     old_state, health.state, final_damage
                 );
+
+                if health.state == HealthState::Broken {
+                    commands.entity(event.defender).insert(FinalStand);
+                    info!("FINAL STAND! {:?} is Broken - next parry is Perfect", event.defender);
+                }
             } else {
                 debug!(
                     "Damage applied: {:.1} HP (blocked={}, counter={}, momentum={:.2}x)",
@@ -72,12 +82,12 @@ pub fn visualize_health_state(
 
 /// Apply movement speed modifiers based on health state
 pub fn apply_movement_speed_modifier(
+    config: Res<GameConfig>,
     mut query: Query<(&Health, &mut crate::components::character::MaxSpeed), Changed<Health>>,
 ) {
     for (health, mut max_speed) in query.iter_mut() {
-        // Reset to base speed (300.0) then apply health modifier
-        let base_speed = 300.0;
-        max_speed.0 = base_speed * health.state.movement_speed_multiplier();
+        // Reset to base walk speed then apply health modifier
+        max_speed.0 = config.walk_speed * health.state.movement_speed_multiplier();
     }
 }
 
@@ -87,7 +97,7 @@ pub fn apply_frame_advantage_penalty(
     mut query: Query<(&Health, &mut crate::components::initiative::Initiative)>,
 ) {
     for event in hit_events.read() {
-        if event.was_blocked {
+        if event.was_blocked || event.parried {
             continue;
         }
 
@@ -110,10 +120,6 @@ pub fn modify_guard_fill_rate(
     mut query: Query<(&Health, &mut crate::components::guard::GuardMeter), Changed<Health>>,
 ) {
     for (health, mut guard) in query.iter_mut() {
-        // Base fill amounts (from guard.rs)
-        let light_fill = 0.15;
-        let heavy_fill = 0.35;
-
         // Apply health state multiplier to fill rates
         let multiplier = health.state.guard_fill_multiplier();
 
@@ -121,11 +127,9 @@ pub fn modify_guard_fill_rate(
         // Note: This assumes we'll modify guard.rs to use these values
         // For now, we'll just log it as the guard system needs to be updated
         debug!(
-            "Guard fill rate modifier for {:?}: {:.1}x (light={:.2}, heavy={:.2})",
+            "Guard fill rate modifier for {:?}: {:.1}x",
             health.state,
-            multiplier,
-            light_fill * multiplier,
-            heavy_fill * multiplier
+            multiplier
         );
     }
 }
@@ -1,49 +1,30 @@
 use bevy::prelude::*;
 use crate::components::hitstop::Hitstop;
-use crate::components::state::AttackData;
 use crate::events::combat_events::HitEvent;
 
 /// Apply hitstop to both attacker and defender when an attack connects
+///
+/// Duration comes from the move's `hitstop_on_hit/block/counter`, resolved
+/// into the `HitEvent` by collision detection.
 pub fn apply_hitstop_on_hit(
     mut commands: Commands,
     mut hit_events: EventReader<HitEvent>,
-    attack_query: Query<&AttackData>,
 ) {
     for event in hit_events.read() {
-        // Get attack data to determine hitstop duration
-        if let Ok(attack_data) = attack_query.get(event.attacker) {
-            // Determine hitstop duration based on hit type and attack strength
-            let is_blocked = event.was_blocked;
-            let is_counter = event.counter_hit;
-
-            // Use default hitstop values based on attack type
-            // Light: 9f hit / 6f block / 12f counter
-            // Heavy: 13f hit / 10f block / 16f counter
-            let (base_hit, base_block, base_counter) = match attack_data.attack_type {
-                crate::components::state::AttackType::Light => (9, 6, 12),
-                crate::components::state::AttackType::Heavy => (13, 10, 16),
-                crate::components::state::AttackType::Grab => (11, 0, 14),
-            };
-
-            let hitstop_frames = if is_counter {
-                base_counter
-            } else if is_blocked {
-                base_block
-            } else {
-                base_hit
-            };
+        if event.hitstop == 0 {
+            continue;
+        }
 
-            debug!(
-                "Applying hitstop: {} frames (blocked: {}, counter: {}, type: {:?})",
-                hitstop_frames, is_blocked, is_counter, attack_data.attack_type
-            );
+        debug!(
+            "Applying hitstop: {} frames (blocked: {}, counter: {}, move: {:?})",
+            event.hitstop, event.was_blocked, event.counter_hit, event.move_id
+        );
 
-            // Apply hitstop to attacker
-            commands.entity(event.attacker).insert(Hitstop::new(hitstop_frames));
+        // Apply hitstop to attacker
+        commands.entity(event.attacker).insert(Hitstop::new(event.hitstop));
 
-            // Apply hitstop to defender
-            commands.entity(event.defender).insert(Hitstop::new(hitstop_frames));
-        }
+        // Apply hitstop to defender
+        commands.entity(event.defender).insert(Hitstop::new(event.hitstop));
     }
 }
 
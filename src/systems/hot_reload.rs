@@ -0,0 +1,241 @@
+use bevy::asset::{AssetLoadError, AssetLoadFailedEvent, AssetPath};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use crate::components::breath::MatchState;
+use crate::components::character::{Character, MaxSpeed};
+use crate::components::health::Health;
+use crate::components::hitstop::Hitstop;
+use crate::components::movelist::Movelist;
+use crate::components::state::CharacterState;
+use crate::components::stumble::StumbleState;
+use crate::data::character::{CharacterData, Characters};
+use crate::data::game_config::{GameConfig, GameConfigHandle};
+use crate::systems::replay::ReplayRecorder;
+use crate::systems::simulation::SimulationDriver;
+use crate::systems::sync_test::SyncTest;
+
+/// Seconds the reload notice stays on screen
+const NOTICE_SECONDS: f32 = 4.0;
+
+/// Edited data files waiting for a neutral moment to be swapped in
+///
+/// Filled as soon as a file is re-read; applied by `apply_pending_reload`.
+#[derive(Resource, Debug, Default)]
+pub struct PendingReload {
+    movelists: HashMap<String, Movelist>,
+    config: Option<GameConfig>,
+}
+
+/// Sent when reloaded data (or a failed reload) takes effect
+#[derive(Event, Debug, Clone)]
+pub struct DataReloaded {
+    /// One line per change, shown on screen
+    pub changes: Vec<String>,
+}
+
+/// Queue a character's movelist when its file changes on disk
+///
+/// Bevy re-reads files itself when built with the `file_watcher` feature;
+/// a reload that fails to parse or validate keeps the old data and is
+/// reported straight away.
+pub fn detect_character_changes(
+    mut events: EventReader<AssetEvent<CharacterData>>,
+    mut failures: EventReader<AssetLoadFailedEvent<CharacterData>>,
+    assets: Res<Assets<CharacterData>>,
+    characters: Res<Characters>,
+    mut pending: ResMut<PendingReload>,
+    mut reloaded: EventWriter<DataReloaded>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else { continue };
+        let Some(name) = characters.handles.iter().find(|(_, handle)| handle.id() == *id).map(|(name, _)| name) else {
+            continue;
+        };
+        if let Some(character) = assets.get(*id) {
+            info!("Hot reload: character '{}' changed on disk", name);
+            pending.movelists.insert(name.clone(), character.movelist());
+        }
+    }
+
+    for failure in failures.read() {
+        report_failure(&failure.path, &failure.error, &mut reloaded);
+    }
+}
+
+/// Queue the game config when its file changes on disk
+pub fn detect_config_changes(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    mut failures: EventReader<AssetLoadFailedEvent<GameConfig>>,
+    configs: Res<Assets<GameConfig>>,
+    handle: Res<GameConfigHandle>,
+    mut pending: ResMut<PendingReload>,
+    mut reloaded: EventWriter<DataReloaded>,
+) {
+    for event in events.read() {
+        if !event.is_modified(&handle.0) {
+            continue;
+        }
+        if let Some(config) = configs.get(&handle.0) {
+            info!("Hot reload: game config changed on disk");
+            pending.config = Some(config.clone());
+        }
+    }
+
+    for failure in failures.read() {
+        report_failure(&failure.path, &failure.error, &mut reloaded);
+    }
+}
+
+fn report_failure(path: &AssetPath, error: &AssetLoadError, reloaded: &mut EventWriter<DataReloaded>) {
+    error!("Hot reload: {} - keeping the previous data: {}", path, error);
+    reloaded.send(DataReloaded {
+        changes: vec![format!("{} not reloaded: {}", path, error)],
+    });
+}
+
+/// Fighter states `is_neutral` looks at
+type NeutralQuery<'w, 's> =
+    Query<'w, 's, (&'static CharacterState, Option<&'static Hitstop>, Option<&'static StumbleState>), With<Character>>;
+
+/// Nobody is mid-attack, blocking, in hitstop or stumbling
+fn is_neutral(fighters: &NeutralQuery) -> bool {
+    fighters.iter().all(|(state, hitstop, stumble)| {
+        matches!(state, CharacterState::Idle | CharacterState::Walking) && hitstop.is_none() && stumble.is_none()
+    })
+}
+
+/// Whether reloaded data may be swapped into a running match
+///
+/// Netplay peers and replays must keep simulating the data they started
+/// with, and a sync test would report the swap as a desync. A local match
+/// being recorded splits its replay at the swap (see `ReplayRecorder`).
+pub fn can_swap_in_match(driver: Res<SimulationDriver>, sync_test: Option<Res<SyncTest>>) -> bool {
+    *driver == SimulationDriver::Local && sync_test.is_none()
+}
+
+/// Swap pending data in at the next neutral moment
+///
+/// Movelists and tuning take effect immediately; breaths, max health and
+/// round length apply from the next round or match, like a fresh spawn. A
+/// match being recorded starts a new replay segment from the next frame.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_pending_reload(
+    mut pending: ResMut<PendingReload>,
+    mut characters: ResMut<Characters>,
+    mut config: ResMut<GameConfig>,
+    match_state: Option<ResMut<MatchState>>,
+    neutral: NeutralQuery,
+    mut fighters: Query<(&mut Movelist, &mut MaxSpeed, &Health), With<Character>>,
+    recorder: Option<ResMut<ReplayRecorder>>,
+    mut reloaded: EventWriter<DataReloaded>,
+) {
+    if pending.movelists.is_empty() && pending.config.is_none() {
+        return;
+    }
+    if !is_neutral(&neutral) {
+        return;
+    }
+
+    let mut changes = Vec::new();
+
+    for (name, movelist) in std::mem::take(&mut pending.movelists) {
+        let changed = characters
+            .movelist(&name)
+            .map(|old| old.changed_moves(&movelist))
+            .unwrap_or_default();
+        if changed.is_empty() {
+            continue;
+        }
+        changes.push(format!("{}: {}", name, changed.join(", ")));
+
+        // Every fighter uses the default character for now
+        for (mut fighter_movelist, _, _) in fighters.iter_mut() {
+            *fighter_movelist = movelist.clone();
+        }
+        characters.movelists.insert(name, movelist);
+    }
+
+    if let Some(new_config) = pending.config.take() {
+        let changed = config.changed_fields(&new_config);
+        if !changed.is_empty() {
+            changes.push(format!("game config: {}", changed.join(", ")));
+
+            for (_, mut max_speed, health) in fighters.iter_mut() {
+                max_speed.0 = new_config.walk_speed * health.state.movement_speed_multiplier();
+            }
+            if let Some(mut match_state) = match_state {
+                match_state.max_round_frames = new_config.round_frames;
+                match_state.countdown_length = new_config.countdown_frames;
+            }
+            *config = new_config;
+        }
+    }
+
+    if changes.is_empty() {
+        return;
+    }
+    if let Some(mut recorder) = recorder {
+        recorder.split_segment();
+    }
+    for change in &changes {
+        info!("Hot reload applied - {}", change);
+    }
+    reloaded.send(DataReloaded { changes });
+}
+
+// ==================== NOTICE ====================
+
+/// On-screen text listing the last reload, removed after `NOTICE_SECONDS`
+#[derive(Component)]
+pub struct ReloadNotice {
+    seconds_left: f32,
+}
+
+/// Show what a reload changed in the top-left corner
+pub fn show_reload_notice(
+    mut commands: Commands,
+    mut events: EventReader<DataReloaded>,
+    mut notices: Query<(&mut Text, &mut ReloadNotice)>,
+) {
+    let Some(event) = events.read().last() else {
+        return;
+    };
+    let text = format!("Hot reload\n{}", event.changes.join("\n"));
+
+    if let Ok((mut notice_text, mut notice)) = notices.get_single_mut() {
+        notice_text.sections[0].value = text;
+        notice.seconds_left = NOTICE_SECONDS;
+        return;
+    }
+
+    commands.spawn((
+        ReloadNotice { seconds_left: NOTICE_SECONDS },
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.6, 1.0, 0.6),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(90.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    ));
+}
+
+/// Remove the notice once it has been up long enough
+pub fn expire_reload_notice(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut notices: Query<(Entity, &mut ReloadNotice)>,
+) {
+    for (entity, mut notice) in notices.iter_mut() {
+        notice.seconds_left -= time.delta_seconds();
+        if notice.seconds_left <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
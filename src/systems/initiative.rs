@@ -1,77 +1,89 @@
 use bevy::prelude::*;
 use crate::components::initiative::Initiative;
 use crate::components::character::Player;
+use crate::components::guard::{ParryGrade, FINAL_STAND_INITIATIVE_STEAL};
+use crate::components::movelist::Movelist;
+use crate::components::state::CharacterState;
 use crate::events::combat_events::{HitEvent, ParryEvent};
 
-/// Apply frame advantage after hits and blocks
+/// Set initiative from the real frame difference after hits and blocks
+///
+/// The attacker is ahead by however much longer the defender stays stunned
+/// than the attacker's own move lasts: the move's `on_hit` / `on_block`,
+/// plus any counter hit bonus or stagger the hit turned into. Runs after the
+/// reactions (and guard break), so the defender's state holds the stun it
+/// actually got.
 pub fn apply_frame_advantage(
     mut hit_events: EventReader<HitEvent>,
+    states: Query<&CharacterState>,
     mut query: Query<(&mut Initiative, &Player)>,
 ) {
     for event in hit_events.read() {
-        // Attacker gains/loses frames based on hit/block
-        if let Ok((mut attacker_init, attacker_player)) = query.get_mut(event.attacker) {
-            if event.was_blocked {
-                // Blocked attacks give disadvantage
-                let disadvantage = match event.damage {
-                    1 => 2,  // Light: -2f on block
-                    2 => 8,  // Heavy: -8f on block
-                    _ => 2,
-                };
-                attacker_init.lose(disadvantage);
-                info!("Player {:?} blocked, -{}f disadvantage", attacker_player, disadvantage);
-            } else {
-                // Successful hits give advantage
-                let advantage = match event.damage {
-                    1 => 4,  // Light: +4f on hit
-                    2 => 6,  // Heavy: +6f on hit
-                    _ => 4,
-                };
-                attacker_init.gain(advantage);
-                info!("Player {:?} hit, +{}f advantage", attacker_player, advantage);
-            }
+        // An absorbed Light lost the read; the Heavy keeps its timing. A
+        // parried hit is settled by `apply_parry_advantage` instead
+        if event.absorbed || event.parried {
+            continue;
         }
 
-        // Defender loses frames if hit (opposite of attacker)
+        let stun_frames = match states.get(event.defender) {
+            Ok(
+                CharacterState::Hitstun { frames_remaining }
+                | CharacterState::Blockstun { frames_remaining }
+                | CharacterState::Staggered { frames_remaining, .. },
+            ) => *frames_remaining,
+            _ => event.stun_frames,
+        };
+        let advantage = event.frame_advantage(stun_frames);
+
+        if let Ok((mut attacker_init, attacker_player)) = query.get_mut(event.attacker) {
+            attacker_init.frames = advantage;
+            info!(
+                "Player {:?} {} {:+}f",
+                attacker_player,
+                if event.was_blocked { "blocked," } else { "hit," },
+                advantage
+            );
+        }
         if let Ok((mut defender_init, defender_player)) = query.get_mut(event.defender) {
-            if !event.was_blocked {
-                let disadvantage = match event.damage {
-                    1 => 4,  // Light hit: -4f
-                    2 => 6,  // Heavy hit: -6f
-                    _ => 4,
-                };
-                defender_init.lose(disadvantage);
-                debug!("Player {:?} got hit, -{}f disadvantage", defender_player, disadvantage);
-            } else {
-                // Defender gains small advantage for successful block
-                let advantage = match event.damage {
-                    1 => 2,  // +2f for blocking Light
-                    2 => 8,  // +8f for blocking Heavy
-                    _ => 2,
-                };
-                defender_init.gain(advantage);
-                debug!("Player {:?} blocked successfully, +{}f advantage", defender_player, advantage);
-            }
+            defender_init.frames = -advantage;
+            debug!("Player {:?} at {:+}f", defender_player, -advantage);
         }
     }
 }
 
-/// Apply massive frame advantage on successful parry
+/// Steal initiative on a Perfect parry
+///
+/// How much is the parrying character's `initiative_steal`, or the full
+/// `FINAL_STAND_INITIATIVE_STEAL` for a Final Stand parry; a Late parry
+/// only negates the hit and changes nothing here.
 pub fn apply_parry_advantage(
     mut parry_events: EventReader<ParryEvent>,
+    movelists: Query<&Movelist>,
     mut query: Query<(&mut Initiative, &Player)>,
 ) {
     for event in parry_events.read() {
+        if event.grade != ParryGrade::Perfect {
+            continue;
+        }
+        let advantage = if event.final_stand {
+            FINAL_STAND_INITIATIVE_STEAL
+        } else {
+            let Ok(movelist) = movelists.get(event.defender) else {
+                continue;
+            };
+            movelist.parry().initiative_steal
+        };
+
         // Defender (parrier) gains huge advantage
         if let Ok((mut defender_init, defender_player)) = query.get_mut(event.defender) {
-            defender_init.gain(12);  // +12f advantage
-            info!("Player {:?} parried! +12f advantage", defender_player);
+            defender_init.gain(advantage);
+            info!("Player {:?} parried! +{}f advantage", defender_player, advantage);
         }
 
         // Attacker loses frames (already staggered, but track it)
         if let Ok((mut attacker_init, attacker_player)) = query.get_mut(event.attacker) {
-            attacker_init.lose(12);  // -12f disadvantage
-            debug!("Player {:?} got parried, -12f disadvantage", attacker_player);
+            attacker_init.lose(advantage);
+            debug!("Player {:?} got parried, -{}f disadvantage", attacker_player, advantage);
         }
     }
 }
@@ -1,9 +1,25 @@
 use bevy::prelude::*;
-use crate::components::character::Player;
+use crate::components::character::Facing;
 use crate::components::movelist::AttackDirection;
+use crate::components::state::AttackType;
+
+// Bit layout used when sending inputs over the network or recording them
+const BIT_LEFT: u16 = 1 << 0;
+const BIT_RIGHT: u16 = 1 << 1;
+const BIT_UP: u16 = 1 << 2;
+const BIT_DOWN: u16 = 1 << 3;
+const BIT_LIGHT: u16 = 1 << 4;
+const BIT_HEAVY: u16 = 1 << 5;
+const BIT_GRAB: u16 = 1 << 6;
+const BIT_BLOCK: u16 = 1 << 7;
+const BIT_STEP: u16 = 1 << 8;
+const BIT_BACKDASH: u16 = 1 << 9;
+const BIT_LIGHT_HELD: u16 = 1 << 10;
+const BIT_HEAVY_HELD: u16 = 1 << 11;
+const BIT_GRAB_HELD: u16 = 1 << 12;
 
 /// Raw input state for each player
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct PlayerInput {
     pub movement: Vec2,      // -1 to 1 for left/right, Y for up/down
     pub light_attack: bool,
@@ -12,14 +28,17 @@ pub struct PlayerInput {
     pub block: bool,
     pub step: bool,          // Quick dash
     pub backdash: bool,
+    // Attack buttons still down (the fields above are presses), for stances
+    pub light_held: bool,
+    pub heavy_held: bool,
+    pub grab_held: bool,
 }
 
 impl PlayerInput {
     /// Get attack direction from movement input
     /// Takes into account which direction the player is facing
-    pub fn get_attack_direction(&self, _player: Player, opponent_x: f32, player_x: f32) -> AttackDirection {
-        // Determine if player is facing right (opponent is to the right)
-        let facing_right = opponent_x > player_x;
+    pub fn get_attack_direction(&self, facing: Facing) -> AttackDirection {
+        let facing_right = facing == Facing::Right;
 
         let holding_down = self.movement.y < -0.5;
         let holding_horizontal = self.movement.x.abs() > 0.5;
@@ -46,6 +65,74 @@ impl PlayerInput {
             AttackDirection::Neutral
         }
     }
+
+    /// Whether the button for `attack_type` is held down
+    pub fn is_held(&self, attack_type: AttackType) -> bool {
+        match attack_type {
+            AttackType::Light => self.light_held,
+            AttackType::Heavy => self.heavy_held,
+            AttackType::Grab => self.grab_held,
+        }
+    }
+
+    /// Merge a freshly sampled input, keeping presses no tick has consumed yet
+    pub fn latch(&mut self, sampled: PlayerInput) {
+        let light_attack = self.light_attack || sampled.light_attack;
+        let heavy_attack = self.heavy_attack || sampled.heavy_attack;
+        let grab = self.grab || sampled.grab;
+
+        *self = PlayerInput {
+            light_attack,
+            heavy_attack,
+            grab,
+            ..sampled
+        };
+    }
+
+    /// Clear one-shot button presses once a tick has consumed them
+    pub fn clear_presses(&mut self) {
+        self.light_attack = false;
+        self.heavy_attack = false;
+        self.grab = false;
+    }
+
+    /// Pack into 16 bits (movement is digital, only its sign is kept)
+    pub fn to_bits(self) -> u16 {
+        let flag = |set: bool, bit: u16| if set { bit } else { 0 };
+
+        flag(self.movement.x < 0.0, BIT_LEFT)
+            | flag(self.movement.x > 0.0, BIT_RIGHT)
+            | flag(self.movement.y > 0.0, BIT_UP)
+            | flag(self.movement.y < 0.0, BIT_DOWN)
+            | flag(self.light_attack, BIT_LIGHT)
+            | flag(self.heavy_attack, BIT_HEAVY)
+            | flag(self.grab, BIT_GRAB)
+            | flag(self.block, BIT_BLOCK)
+            | flag(self.step, BIT_STEP)
+            | flag(self.backdash, BIT_BACKDASH)
+            | flag(self.light_held, BIT_LIGHT_HELD)
+            | flag(self.heavy_held, BIT_HEAVY_HELD)
+            | flag(self.grab_held, BIT_GRAB_HELD)
+    }
+
+    /// Unpack from the 16-bit representation produced by `to_bits`
+    pub fn from_bits(bits: u16) -> Self {
+        let held = |bit: u16| bits & bit != 0;
+        let axis = |negative: u16, positive: u16| held(positive) as i8 as f32 - held(negative) as i8 as f32;
+
+        Self {
+            movement: Vec2::new(axis(BIT_LEFT, BIT_RIGHT), axis(BIT_DOWN, BIT_UP)),
+            light_attack: held(BIT_LIGHT),
+            heavy_attack: held(BIT_HEAVY),
+            grab: held(BIT_GRAB),
+            block: held(BIT_BLOCK),
+            step: held(BIT_STEP),
+            backdash: held(BIT_BACKDASH),
+            light_held: held(BIT_LIGHT_HELD),
+            heavy_held: held(BIT_HEAVY_HELD),
+            grab_held: held(BIT_GRAB_HELD),
+        }
+    }
 }
 
 /// Get Player 1 input from keyboard
@@ -70,6 +157,9 @@ pub fn get_p1_input(keys: &ButtonInput<KeyCode>) -> PlayerInput {
     input.light_attack = keys.just_pressed(KeyCode::KeyJ);
     input.heavy_attack = keys.just_pressed(KeyCode::KeyK);
     input.grab = keys.just_pressed(KeyCode::KeyL);
+    input.light_held = keys.pressed(KeyCode::KeyJ);
+    input.heavy_held = keys.pressed(KeyCode::KeyK);
+    input.grab_held = keys.pressed(KeyCode::KeyL);
     input.block = keys.pressed(KeyCode::KeyI);  // I for block
 
     // Movement options (Shift + direction) - only for evade, not attacks
@@ -103,6 +193,9 @@ pub fn get_p2_input(keys: &ButtonInput<KeyCode>) -> PlayerInput {
     input.light_attack = keys.just_pressed(KeyCode::Numpad1);
     input.heavy_attack = keys.just_pressed(KeyCode::Numpad2);
     input.grab = keys.just_pressed(KeyCode::Numpad3);
+    input.light_held = keys.pressed(KeyCode::Numpad1);
+    input.heavy_held = keys.pressed(KeyCode::Numpad2);
+    input.grab_held = keys.pressed(KeyCode::Numpad3);
     input.block = keys.pressed(KeyCode::Numpad0);
 
     // Movement options (RShift + direction) - only for evade, not attacks
@@ -115,17 +208,40 @@ pub fn get_p2_input(keys: &ButtonInput<KeyCode>) -> PlayerInput {
 }
 
 /// Resource to store current frame's inputs
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
 pub struct CurrentInputs {
     pub player_one: PlayerInput,
     pub player_two: PlayerInput,
 }
 
-/// System to update input resource each frame
-pub fn update_inputs(
+/// Inputs sampled since the last simulation tick
+///
+/// The keyboard is only polled once per rendered frame, which may run zero or
+/// several simulation ticks. Presses are latched here so each one reaches
+/// exactly one tick.
+#[derive(Resource, Default)]
+pub struct LatchedInputs {
+    pub player_one: PlayerInput,
+    pub player_two: PlayerInput,
+}
+
+/// Sample the keyboard once per rendered frame
+pub fn latch_inputs(
     keys: Res<ButtonInput<KeyCode>>,
+    mut latched: ResMut<LatchedInputs>,
+) {
+    latched.player_one.latch(get_p1_input(&keys));
+    latched.player_two.latch(get_p2_input(&keys));
+}
+
+/// Hand latched inputs to the upcoming simulation tick
+pub fn update_inputs(
+    mut latched: ResMut<LatchedInputs>,
     mut inputs: ResMut<CurrentInputs>,
 ) {
-    inputs.player_one = get_p1_input(&keys);
-    inputs.player_two = get_p2_input(&keys);
+    inputs.player_one = latched.player_one;
+    inputs.player_two = latched.player_two;
+
+    latched.player_one.clear_presses();
+    latched.player_two.clear_presses();
 }
@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::character::Player;
+use crate::components::movelist::{AttackDirection, Movelist};
+use crate::components::state::{AttackPhase, AttackType, CharacterState, StateTimer};
+use crate::data::game_config::GameConfig;
+use crate::events::combat_events::HitEvent;
+use crate::systems::attack::create_hitbox;
+use crate::systems::input::CurrentInputs;
+use crate::systems::simulation::FRAME_SECONDS;
+
+/// Height of a fighter's feet below its centre, where the jump shadow sits
+const FEET_OFFSET: f32 = 100.0;
+
+/// A fighter off the ground, following the jump arc
+///
+/// Inserted at take-off and removed on touching down. The sideways speed is
+/// fixed at take-off; only gravity changes the arc after that, so a jump
+/// can't be steered once committed.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct JumpArc {
+    /// Pixels per second
+    pub velocity: Vec2,
+    /// The one aerial attack of this jump has been used (or lost to a hit)
+    pub attack_used: bool,
+}
+
+impl JumpArc {
+    pub fn new(velocity: Vec2) -> Self {
+        Self { velocity, attack_used: false }
+    }
+
+    /// Advance one frame under `gravity`; true once back on the ground
+    pub fn step(&mut self, translation: &mut Vec3, gravity: f32) -> bool {
+        self.velocity.y -= gravity * FRAME_SECONDS;
+        translation.x += self.velocity.x * FRAME_SECONDS;
+        translation.y += self.velocity.y * FRAME_SECONDS;
+
+        if translation.y <= 0.0 {
+            translation.y = 0.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Up starts a jump from neutral (Shift + Up is still an evade)
+///
+/// Runs after the attack, block and evade inputs, so any of those pressed
+/// on the same frame wins.
+pub fn handle_jump_input(
+    config: Res<GameConfig>,
+    inputs: Res<CurrentInputs>,
+    mut query: Query<(&Player, &mut CharacterState)>,
+) {
+    for (player, mut state) in query.iter_mut() {
+        if !matches!(*state, CharacterState::Idle | CharacterState::Walking) {
+            continue;
+        }
+
+        let input = match player {
+            Player::One => &inputs.player_one,
+            Player::Two => &inputs.player_two,
+        };
+
+        if input.movement.y > 0.5 && !input.step {
+            *state = CharacterState::Prejump {
+                frames_remaining: config.jump_prejump_frames,
+            };
+            info!("Player {:?} jumping", player);
+        }
+    }
+}
+
+/// Attack in the air with the character's aerial move, once per jump
+///
+/// Only moves listed under the `Air` direction can be used; buttons without
+/// one do nothing in the air.
+pub fn handle_air_attack_input(
+    mut commands: Commands,
+    inputs: Res<CurrentInputs>,
+    mut query: Query<(Entity, &Player, &mut CharacterState, &mut JumpArc, &Movelist)>,
+) {
+    for (entity, player, mut state, mut arc, movelist) in query.iter_mut() {
+        if *state != CharacterState::Airborne || arc.attack_used {
+            continue;
+        }
+
+        let input = match player {
+            Player::One => &inputs.player_one,
+            Player::Two => &inputs.player_two,
+        };
+
+        let attack_type = if input.light_attack {
+            AttackType::Light
+        } else if input.heavy_attack {
+            AttackType::Heavy
+        } else if input.grab {
+            AttackType::Grab
+        } else {
+            continue;
+        };
+
+        let Some(move_id) = movelist.resolve(attack_type, AttackDirection::Air) else {
+            continue;
+        };
+        let Some(move_data) = movelist.get(move_id) else {
+            continue;
+        };
+
+        *state = CharacterState::Attacking {
+            attack_type,
+            direction: AttackDirection::Air,
+            phase: AttackPhase::Startup,
+        };
+        arc.attack_used = true;
+        commands.entity(entity).insert((StateTimer::new(move_data.startup_frames), create_hitbox(move_data)));
+
+        info!("Player {:?} aerial attack: {}", player, move_data.name);
+    }
+}
+
+/// Count prejump and landing recovery down, taking off when prejump ends
+///
+/// Forward or back held at take-off sets the sideways drift for the whole
+/// arc.
+pub fn progress_jump(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    inputs: Res<CurrentInputs>,
+    mut query: Query<(Entity, &Player, &mut CharacterState)>,
+) {
+    for (entity, player, mut state) in query.iter_mut() {
+        match &mut *state {
+            CharacterState::Prejump { frames_remaining } => {
+                *frames_remaining = frames_remaining.saturating_sub(1);
+                if *frames_remaining > 0 {
+                    continue;
+                }
+
+                let input = match player {
+                    Player::One => &inputs.player_one,
+                    Player::Two => &inputs.player_two,
+                };
+                let drift = if input.movement.x.abs() > 0.5 {
+                    input.movement.x.signum() * config.jump_drift_speed
+                } else {
+                    0.0
+                };
+
+                commands.entity(entity).insert(JumpArc::new(Vec2::new(drift, config.jump_velocity)));
+                *state = CharacterState::Airborne;
+            }
+            CharacterState::Landing { frames_remaining } => {
+                *frames_remaining = frames_remaining.saturating_sub(1);
+                if *frames_remaining == 0 {
+                    *state = CharacterState::Idle;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Move airborne fighters along their arc and land them
+///
+/// Touching down out of the jump or the aerial attack (cutting its recovery
+/// short) goes into landing recovery; a fighter hit out of the air lands
+/// still in hitstun.
+pub fn apply_jump_arc(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut query: Query<(Entity, &mut CharacterState, &mut Transform, &mut JumpArc)>,
+) {
+    for (entity, mut state, mut transform, mut arc) in query.iter_mut() {
+        // Hitstun or the aerial attack ran out mid-air: still falling
+        if matches!(*state, CharacterState::Idle | CharacterState::Walking) {
+            *state = CharacterState::Airborne;
+        }
+
+        if !arc.step(&mut transform.translation, config.jump_gravity) {
+            continue;
+        }
+
+        commands.entity(entity).remove::<JumpArc>();
+        if matches!(
+            *state,
+            CharacterState::Airborne | CharacterState::Attacking { direction: AttackDirection::Air, .. }
+        ) {
+            *state = CharacterState::Landing {
+                frames_remaining: config.jump_landing_frames,
+            };
+            commands.entity(entity).remove::<StateTimer>();
+        }
+        debug!("Fighter {:?} landed", entity);
+    }
+}
+
+/// A hit in the air kills the jump: the fighter drops straight down in
+/// hitstun and loses its aerial attack
+pub fn knock_out_of_air(
+    mut hit_events: EventReader<HitEvent>,
+    mut query: Query<&mut JumpArc>,
+) {
+    for event in hit_events.read() {
+        if event.was_blocked || event.parried || event.absorbed {
+            continue;
+        }
+        if let Ok(mut arc) = query.get_mut(event.defender) {
+            arc.velocity.x = 0.0;
+            arc.velocity.y = arc.velocity.y.min(0.0);
+            arc.attack_used = true;
+            info!("ANTI-AIR! {:?} knocked out of the air", event.defender);
+        }
+    }
+}
+
+/// Shadow on the ground under an airborne fighter, shrinking with height
+pub fn visualize_jump(
+    mut gizmos: Gizmos,
+    query: Query<&Transform, With<JumpArc>>,
+) {
+    for transform in query.iter() {
+        let height = transform.translation.y;
+        let scale = (1.0 - height / 400.0).clamp(0.4, 1.0);
+        gizmos.ellipse_2d(
+            Vec2::new(transform.translation.x, -FEET_OFFSET),
+            0.0,
+            Vec2::new(45.0 * scale, 8.0 * scale),
+            Color::srgba(0.0, 0.0, 0.0, 0.5),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arc_is_fixed_and_lands() {
+        let mut arc = JumpArc::new(Vec2::new(220.0, 1000.0));
+        let mut position = Vec3::ZERO;
+        let mut apex: f32 = 0.0;
+        let mut frames = 0;
+
+        while !arc.step(&mut position, 3200.0) {
+            apex = apex.max(position.y);
+            frames += 1;
+            assert!(frames < 120, "jump never landed");
+        }
+
+        // Roughly v²/2g high, and back on the ground having drifted forward
+        assert!((apex - 156.25).abs() < 10.0, "apex {}", apex);
+        assert_eq!(position.y, 0.0);
+        assert!(position.x > 0.0);
+        assert!((36..=40).contains(&frames), "{} frames in the air", frames);
+    }
+}
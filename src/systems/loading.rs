@@ -0,0 +1,99 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use crate::components::movelist::DEFAULT_CHARACTER;
+use crate::data::character::{character_path, CharacterData, Characters};
+use crate::data::game_config::{GameConfig, GameConfigHandle, GAME_CONFIG_PATH};
+use crate::systems::game_state::GameState;
+
+/// State to enter once the game config and character files have loaded
+///
+/// Character select by default; netplay and replay playback go straight to
+/// their match.
+#[derive(Resource, Debug, Clone)]
+pub struct StartState(pub GameState);
+
+impl Default for StartState {
+    fn default() -> Self {
+        Self(GameState::CharacterSelect)
+    }
+}
+
+/// Start loading the game config and every character file
+pub fn load_game_data(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut characters: ResMut<Characters>,
+) {
+    info!("Loading {}", GAME_CONFIG_PATH);
+    commands.insert_resource(GameConfigHandle(asset_server.load(GAME_CONFIG_PATH)));
+
+    let path = character_path(DEFAULT_CHARACTER);
+    info!("Loading character {}", path);
+    characters.handles.insert(DEFAULT_CHARACTER.to_string(), asset_server.load(path));
+}
+
+/// Insert the `GameConfig` resource once the config file has loaded
+///
+/// A file that fails to load, parse or validate can't be played without, so
+/// the error is logged and the game exits.
+pub fn finish_loading_config(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    configs: Res<Assets<GameConfig>>,
+    handle: Res<GameConfigHandle>,
+    mut exit: EventWriter<AppExit>,
+) {
+    match asset_server.load_state(&handle.0) {
+        LoadState::Loaded => {
+            if let Some(config) = configs.get(&handle.0) {
+                commands.insert_resource(config.clone());
+            }
+        }
+        LoadState::Failed(e) => {
+            error!("{} failed to load: {}", GAME_CONFIG_PATH, e);
+            exit.send(AppExit::error());
+        }
+        LoadState::NotLoaded | LoadState::Loading => {}
+    }
+}
+
+/// Leave `GameState::Loading` once the config and every character have loaded
+///
+/// Character files that fail are handled like the config: logged, then exit.
+pub fn finish_loading(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<CharacterData>>,
+    config: Option<Res<GameConfig>>,
+    start_state: Res<StartState>,
+    mut characters: ResMut<Characters>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let mut all_loaded = config.is_some();
+    let mut loaded = Vec::new();
+    for (name, handle) in characters.handles.iter() {
+        match asset_server.load_state(handle) {
+            LoadState::Loaded => {
+                if let Some(character) = assets.get(handle) {
+                    debug!("Character '{}' loaded as '{}'", name, character.name);
+                    loaded.push((name.clone(), character.movelist()));
+                }
+            }
+            LoadState::Failed(e) => {
+                error!("Character '{}' failed to load: {}", name, e);
+                exit.send(AppExit::error());
+                return;
+            }
+            LoadState::NotLoaded | LoadState::Loading => all_loaded = false,
+        }
+    }
+    if !all_loaded {
+        return;
+    }
+
+    for (name, movelist) in loaded {
+        characters.movelists.insert(name, movelist);
+    }
+    info!("Game config and characters loaded");
+    next_state.set(start_state.0.clone());
+}
@@ -5,18 +5,32 @@ pub mod attack;
 pub mod breath;
 pub mod chain;
 pub mod collision;
+pub mod corner;
 pub mod damage;
+pub mod decisive;
 pub mod evade;
 pub mod game_state;
 pub mod guard;
+pub mod headless;
 pub mod health;
 pub mod hitstop;
+pub mod hot_reload;
 pub mod initiative;
 pub mod input;
+pub mod jump;
+pub mod loading;
 pub mod menus;
 pub mod momentum;
 pub mod movement;
+pub mod netplay;
+pub mod playback;
 pub mod pressure;
+pub mod replay;
+pub mod simulation;
+pub mod snapshot;
+pub mod stance;
 pub mod stumble;
+pub mod sync_test;
+pub mod tenuki;
 pub mod ui;
 pub mod visual_effects;
@@ -1,9 +1,11 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::components::character::Player;
+use crate::components::guard::ParryGrade;
 use crate::events::combat_events::{HitEvent, ParryEvent};
 
 /// Component to track momentum (win streak)
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Momentum {
     /// Current momentum level (0-5)
     pub level: u8,
@@ -15,10 +17,14 @@ pub struct Momentum {
 
 impl Momentum {
     pub fn new() -> Self {
+        Self::with_decay_threshold(120)
+    }
+
+    pub fn with_decay_threshold(decay_threshold: u32) -> Self {
         Self {
             level: 0,
             frames_since_action: 0,
-            decay_threshold: 120,
+            decay_threshold,
         }
     }
 
@@ -90,6 +96,11 @@ pub fn build_momentum_on_hit(
     mut query: Query<&mut Momentum>,
 ) {
     for event in hit_events.read() {
+        // A parried hit earns nothing (see `build_momentum_on_parry`)
+        if event.parried {
+            continue;
+        }
+
         // Attacker gains momentum
         if let Ok(mut momentum) = query.get_mut(event.attacker) {
             momentum.gain();
@@ -114,6 +125,11 @@ pub fn build_momentum_on_parry(
     mut query: Query<&mut Momentum>,
 ) {
     for event in parry_events.read() {
+        // A Late parry only negates the hit
+        if event.grade != ParryGrade::Perfect {
+            continue;
+        }
+
         // Defender (parrier) gains extra momentum
         if let Ok(mut momentum) = query.get_mut(event.defender) {
             momentum.gain();
@@ -1,14 +1,18 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::components::character::*;
+use crate::components::combat::Pushbox;
 use crate::components::state::*;
+use crate::data::game_config::GameConfig;
 use crate::systems::input::CurrentInputs;
+use crate::systems::simulation::FRAME_SECONDS;
 
 use crate::components::state::StateTimer;
 
-const STAGE_WIDTH: f32 = 1000.0;
-const STAGE_HALF_WIDTH: f32 = STAGE_WIDTH / 2.0;
-
+use crate::components::hitstop::Hitstop;
+use crate::components::stumble::StumbleState;
 use crate::systems::evade::EvadeData;
+use crate::systems::jump::JumpArc;
 
 /// Process player inputs and update velocities
 pub fn process_movement_input(
@@ -47,6 +51,37 @@ pub fn process_movement_input(
     }
 }
 
+/// Turn fighters to face each other once they have crossed
+///
+/// Only fighters free to act turn: mid-attack, blocking, stumbling or in
+/// hitstop they keep their facing, which is what makes a cross-up (see
+/// `collision::detect_hits`).
+#[allow(clippy::type_complexity)]
+pub fn update_facing(
+    mut query: Query<(Entity, &Transform, &CharacterState, &mut Facing, Option<&StumbleState>, Option<&Hitstop>), With<Character>>,
+) {
+    let positions: Vec<(Entity, f32)> = query.iter().map(|(e, t, _, _, _, _)| (e, t.translation.x)).collect();
+
+    for (entity, transform, state, mut facing, stumble, hitstop) in query.iter_mut() {
+        if !matches!(state, CharacterState::Idle | CharacterState::Walking) || stumble.is_some() || hitstop.is_some() {
+            continue;
+        }
+        let Some(opponent_x) = positions.iter().find(|(e, _)| *e != entity).map(|(_, x)| *x) else {
+            continue;
+        };
+        let x = transform.translation.x;
+        if opponent_x == x {
+            continue;
+        }
+
+        let towards = Facing::towards(x, opponent_x);
+        if *facing != towards {
+            *facing = towards;
+            debug!("Fighter {:?} turned to face {:?}", entity, towards);
+        }
+    }
+}
+
 /// Update character states based on velocity
 pub fn update_movement_state(
     mut query: Query<(&Velocity, &mut CharacterState), Changed<Velocity>>,
@@ -70,29 +105,89 @@ pub fn update_movement_state(
 
 /// Apply velocity to transform positions
 pub fn apply_velocity(
-    time: Res<Time>,
     mut query: Query<(&Velocity, &mut Transform), With<Character>>,
 ) {
     for (velocity, mut transform) in query.iter_mut() {
-        transform.translation.x += velocity.0.x * time.delta_seconds();
-        transform.translation.y += velocity.0.y * time.delta_seconds();
+        // Velocity is in units per second, applied one simulation frame at a time
+        transform.translation.x += velocity.0.x * FRAME_SECONDS;
+        transform.translation.y += velocity.0.y * FRAME_SECONDS;
     }
 }
 
 /// Clamp characters to stage boundaries
 pub fn clamp_to_stage(
-    mut query: Query<&mut Transform, With<Character>>,
+    config: Res<GameConfig>,
+    mut query: Query<(&mut Transform, Has<JumpArc>), With<Character>>,
 ) {
-    for mut transform in query.iter_mut() {
-        // Clamp X position to stage boundaries
-        transform.translation.x = transform.translation.x.clamp(
-            -STAGE_HALF_WIDTH + 30.0,  // Half character width
-            STAGE_HALF_WIDTH - 30.0,
-        );
+    let limit = config.stage_limit();
+    for (mut transform, airborne) in query.iter_mut() {
+        // Clamp X position to stage boundaries (minus half character width)
+        transform.translation.x = transform.translation.x.clamp(-limit, limit);
+
+        // Keep characters on the ground unless jumping (`jump::apply_jump_arc`)
+        if !airborne {
+            transform.translation.y = 0.0;
+        }
+    }
+}
 
-        // Keep characters on the ground (Y = 0 for now)
-        transform.translation.y = 0.0;
+/// Push overlapping fighters apart so they can't walk through each other
+///
+/// The overlap is shared evenly; a fighter pinned in the corner can't give
+/// ground, so the other takes the whole push. Evading fighters with i-frames
+/// and jumping fighters pass through (that is how sides get switched; a
+/// jumper landing on top is pushed off once down), and stumbling fighters
+/// use their narrower pushbox.
+#[allow(clippy::type_complexity)]
+pub fn resolve_pushboxes(
+    config: Res<GameConfig>,
+    mut query: Query<(&mut Transform, &Pushbox, &Facing, Option<&StumbleState>, Option<&EvadeData>, Has<JumpArc>), With<Character>>,
+) {
+    let mut fighters: Vec<_> = query.iter_mut().collect();
+    let [(a_transform, a_box, a_facing, a_stumble, a_evade, a_airborne), (b_transform, b_box, _, b_stumble, b_evade, b_airborne)] = fighters.as_mut_slice() else {
+        return;
+    };
+    if a_evade.is_some_and(|evade| evade.invincible) || b_evade.is_some_and(|evade| evade.invincible) {
+        return;
+    }
+    if *a_airborne || *b_airborne {
+        return;
     }
+
+    let min_gap = a_box.half_width(a_stumble.is_some()) + b_box.half_width(b_stumble.is_some());
+    let (ax, bx) = (a_transform.translation.x, b_transform.translation.x);
+    if (ax - bx).abs() >= min_gap {
+        return;
+    }
+
+    // Whoever is further left stays on the left; level fighters keep their facing
+    let a_is_left = ax < bx || (ax == bx && **a_facing == Facing::Right);
+    let (left, right) = if a_is_left { (ax, bx) } else { (bx, ax) };
+    let (left, right) = separate(left, right, min_gap, config.stage_limit());
+    let (new_a, new_b) = if a_is_left { (left, right) } else { (right, left) };
+
+    a_transform.translation.x = new_a;
+    b_transform.translation.x = new_b;
+}
+
+/// Move `left` and `right` apart to `min_gap`, splitting the push and
+/// shifting it onto the other fighter at a wall
+fn separate(left: f32, right: f32, min_gap: f32, limit: f32) -> (f32, f32) {
+    let overlap = min_gap - (right - left);
+    if overlap <= 0.0 {
+        return (left, right);
+    }
+
+    let mut left = left - overlap / 2.0;
+    let mut right = right + overlap / 2.0;
+    if left < -limit {
+        left = -limit;
+        right = left + min_gap;
+    } else if right > limit {
+        right = limit;
+        left = right - min_gap;
+    }
+    (left, right)
 }
 
 /// Debug system to visualize character state
@@ -116,7 +211,7 @@ pub fn debug_character_state(
 use crate::components::movelist::{AttackDirection, AttackMovement, Movelist};
 
 /// Component to track attack movement progress
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveAttackMovement {
     /// Total distance to move
     pub total_distance: f32,
@@ -129,13 +224,12 @@ pub struct ActiveAttackMovement {
 }
 
 impl ActiveAttackMovement {
-    pub fn new(movement: &AttackMovement, facing_right: bool) -> Self {
-        let facing_multiplier = if facing_right { 1.0 } else { -1.0 };
+    pub fn new(movement: &AttackMovement, facing: Facing) -> Self {
         Self {
             total_distance: movement.distance,
             distance_moved: 0.0,
             speed: movement.speed,
-            facing_multiplier,
+            facing_multiplier: facing.sign(),
         }
     }
 
@@ -151,10 +245,9 @@ impl ActiveAttackMovement {
 /// Initiate attack movement when entering Active phase with a move that has movement
 pub fn initiate_attack_movement(
     mut commands: Commands,
-    query: Query<(Entity, &CharacterState, &Movelist, &Transform), Changed<CharacterState>>,
-    opponent_query: Query<&Transform, With<Player>>,
+    query: Query<(Entity, &CharacterState, &Movelist, &Facing), Changed<CharacterState>>,
 ) {
-    for (entity, state, movelist, transform) in query.iter() {
+    for (entity, state, movelist, facing) in query.iter() {
         // Only start movement when entering Active phase
         if let CharacterState::Attacking {
             attack_type,
@@ -166,18 +259,8 @@ pub fn initiate_attack_movement(
             if let Some(move_data) = movelist.get_move(*attack_type, *direction) {
                 // Check if this move has movement
                 if let Some(ref movement) = move_data.movement {
-                    // Determine facing direction (based on opponent position)
-                    let player_x = transform.translation.x;
-                    let opponent_x = opponent_query
-                        .iter()
-                        .find(|t| t.translation.x != player_x)
-                        .map(|t| t.translation.x)
-                        .unwrap_or(0.0);
-
-                    let facing_right = opponent_x > player_x;
-
-                    // Add movement tracking component
-                    let active_movement = ActiveAttackMovement::new(movement, facing_right);
+                    // Add movement tracking component (forward = the way the fighter faces)
+                    let active_movement = ActiveAttackMovement::new(movement, *facing);
                     commands.entity(entity).insert(active_movement);
 
                     debug!(
@@ -251,12 +334,10 @@ pub fn cleanup_attack_movement(
 // DASH SYSTEM
 // ============================================================================
 
-const DASH_DISTANCE: f32 = 120.0;  // Fixed distance for dash
-const DASH_SPEED: f32 = 20.0;      // Speed per frame
-const DASH_COOLDOWN: u32 = 30;     // 30 frames = 0.5 seconds at 60fps
+// Distance, speed and cooldown come from `GameConfig` (dash_*)
 
 /// Component to track dash state and cooldown
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct DashData {
     /// Direction of the dash (-1.0 or 1.0)
     pub direction: f32,
@@ -265,15 +346,15 @@ pub struct DashData {
 }
 
 /// Component to track dash cooldown
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct DashCooldown {
     pub frames_remaining: u32,
 }
 
 impl DashCooldown {
-    pub fn new() -> Self {
+    pub fn new(frames: u32) -> Self {
         Self {
-            frames_remaining: DASH_COOLDOWN,
+            frames_remaining: frames,
         }
     }
 
@@ -287,18 +368,22 @@ impl DashCooldown {
         }
     }
 
-    pub fn reset(&mut self) {
-        self.frames_remaining = DASH_COOLDOWN;
+    pub fn reset(&mut self, frames: u32) {
+        self.frames_remaining = frames;
     }
 }
 
 /// Handle dash input (Shift + direction)
+///
+/// A cornered fighter can't backdash into the wall behind it.
+#[allow(clippy::type_complexity)]
 pub fn handle_dash_input(
     mut commands: Commands,
     inputs: Res<CurrentInputs>,
-    mut query: Query<(Entity, &Player, &CharacterState, Option<&mut DashCooldown>, Option<&DashData>)>,
+    config: Res<GameConfig>,
+    mut query: Query<(Entity, &Player, &CharacterState, &Corner, Option<&mut DashCooldown>, Option<&DashData>)>,
 ) {
-    for (entity, player, state, cooldown, dash_data) in query.iter_mut() {
+    for (entity, player, state, corner, cooldown, dash_data) in query.iter_mut() {
         // Can only dash from Idle or Walking state
         if !matches!(state, CharacterState::Idle | CharacterState::Walking) {
             continue;
@@ -323,8 +408,12 @@ pub fn handle_dash_input(
 
         // Check for dash input (step flag is set when shift + direction is pressed)
         if input.step && input.movement.x.abs() > 0.1 {
-            // Initiate dash
             let direction = input.movement.x.signum();
+            if corner.into_wall(direction) {
+                continue;
+            }
+
+            // Initiate dash
             commands.entity(entity).insert(DashData {
                 direction,
                 distance_traveled: 0.0,
@@ -332,9 +421,9 @@ pub fn handle_dash_input(
 
             // Reset/add cooldown
             if let Some(mut cd) = cooldown {
-                cd.reset();
+                cd.reset(config.dash_cooldown);
             } else {
-                commands.entity(entity).insert(DashCooldown::new());
+                commands.entity(entity).insert(DashCooldown::new(config.dash_cooldown));
             }
 
             info!("Player {:?} dashing {:}", player, if direction > 0.0 { "forward" } else { "backward" });
@@ -345,6 +434,7 @@ pub fn handle_dash_input(
 /// Apply dash movement
 pub fn apply_dash_movement(
     mut commands: Commands,
+    config: Res<GameConfig>,
     mut query: Query<(Entity, &mut Transform, &mut DashData, &CharacterState)>,
 ) {
     for (entity, mut transform, mut dash, state) in query.iter_mut() {
@@ -356,11 +446,11 @@ pub fn apply_dash_movement(
         }
 
         // Calculate how much to move this frame
-        let remaining = DASH_DISTANCE - dash.distance_traveled;
-        let move_amount = if remaining < DASH_SPEED {
+        let remaining = config.dash_distance - dash.distance_traveled;
+        let move_amount = if remaining < config.dash_speed {
             remaining
         } else {
-            DASH_SPEED
+            config.dash_speed
         };
 
         // Apply movement
@@ -368,7 +458,7 @@ pub fn apply_dash_movement(
         dash.distance_traveled += move_amount;
 
         // Check if dash is complete
-        if dash.distance_traveled >= DASH_DISTANCE {
+        if dash.distance_traveled >= config.dash_distance {
             commands.entity(entity).remove::<DashData>();
             debug!("Dash complete: {} units", dash.distance_traveled);
         }
@@ -387,6 +477,7 @@ pub fn tick_dash_cooldown(
 /// Visual indicator for dash cooldown
 pub fn visualize_dash_cooldown(
     mut gizmos: Gizmos,
+    config: Res<GameConfig>,
     query: Query<(&Transform, &Player, Option<&DashCooldown>)>,
 ) {
     for (transform, _player, cooldown) in query.iter() {
@@ -394,7 +485,7 @@ pub fn visualize_dash_cooldown(
             if !cooldown.is_ready() {
                 // Draw cooldown indicator
                 let pos = transform.translation.truncate();
-                let cooldown_ratio = cooldown.frames_remaining as f32 / DASH_COOLDOWN as f32;
+                let cooldown_ratio = cooldown.frames_remaining as f32 / config.dash_cooldown.max(1) as f32;
 
                 // Draw a small arc showing cooldown progress
                 let radius = 25.0;
@@ -409,3 +500,22 @@ pub fn visualize_dash_cooldown(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separate_shares_push() {
+        assert_eq!(separate(-10.0, 10.0, 60.0, 470.0), (-30.0, 30.0));
+        // Already apart: untouched
+        assert_eq!(separate(-100.0, 100.0, 60.0, 470.0), (-100.0, 100.0));
+    }
+
+    #[test]
+    fn test_separate_respects_corner() {
+        // Left fighter pinned in the corner: the right one takes the whole push
+        assert_eq!(separate(-470.0, -450.0, 60.0, 470.0), (-470.0, -410.0));
+        assert_eq!(separate(440.0, 470.0, 60.0, 470.0), (410.0, 470.0));
+    }
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use crate::components::movelist::DEFAULT_CHARACTER;
+use crate::data::character::Characters;
+use crate::data::game_config::GameConfig;
+use crate::netplay::protocol::DataFingerprints;
+use crate::netplay::session::RollbackSession;
+use crate::netplay::{Netplay, NetplayConfig};
+use crate::systems::input::{CurrentInputs, LatchedInputs};
+use crate::systems::simulation::{clear_simulation_events, run_simulation_tick};
+use crate::systems::snapshot::SimState;
+
+/// Start a fresh rollback session for each match
+///
+/// The session offers the fingerprints of the data this peer loaded; a peer
+/// with different data is refused in the handshake.
+pub fn start_netplay_session(
+    config: Res<NetplayConfig>,
+    characters: Res<Characters>,
+    game_config: Res<GameConfig>,
+    mut netplay: ResMut<Netplay>,
+) {
+    let fingerprints = DataFingerprints {
+        movelist: characters.movelist(DEFAULT_CHARACTER).map_or(0, |movelist| movelist.fingerprint()),
+        config: game_config.fingerprint(),
+    };
+    netplay.session = RollbackSession::new(config.local_player, config.input_delay, fingerprints);
+    info!(
+        "Netplay: playing as {:?} against {} with {} frames input delay - waiting for peer...",
+        config.local_player, config.peer, config.input_delay
+    );
+}
+
+/// Fixed-tick driver while netplay is active (replaces the local driver)
+///
+/// 1. Read the peer's inputs; if one contradicts a prediction, restore the
+///    snapshot from that frame and resimulate up to the present
+/// 2. Stall if the peer is too far behind
+/// 3. Queue the local input `input_delay` frames ahead and send it
+/// 4. Simulate the next frame with confirmed or predicted remote input
+///
+/// Checksums of confirmed frames are exchanged to detect desyncs.
+pub fn netplay_tick(world: &mut World) {
+    world.resource_scope(|world, mut netplay: Mut<Netplay>| {
+        let netplay = &mut *netplay;
+        let was_connected = netplay.session.is_connected();
+
+        for message in netplay.transport.receive() {
+            netplay.session.handle_message(message);
+        }
+
+        if !was_connected && netplay.session.is_connected() {
+            info!("Netplay: peer connected");
+        }
+        if let Some(theirs) = netplay.session.take_peer_mismatch() {
+            let ours = netplay.session.fingerprints();
+            error!(
+                "Netplay: refusing peer - it runs a different {} (both peers need the same data files)",
+                match (theirs.movelist != ours.movelist, theirs.config != ours.config) {
+                    (true, true) => "movelist and game config",
+                    (true, false) => "movelist",
+                    _ => "game config",
+                }
+            );
+        }
+
+        if let Some(state) = netplay.session.take_rollback() {
+            let present = netplay.session.current_frame();
+            let rollback_frame = state.frame;
+            state.restore(world);
+            // Presentation already showed the predicted frames; the corrected
+            // ones replace them silently
+            clear_simulation_events(world);
+            for frame in rollback_frame..present {
+                simulate_frame(world, &mut netplay.session, frame);
+            }
+            clear_simulation_events(world);
+            debug!("Netplay: rolled back {} frames", present - rollback_frame);
+        }
+
+        netplay.session.checksum_confirmed_frames();
+        if let Some(frame) = netplay.session.take_desync() {
+            error!("Netplay: desync detected - state checksums differ after frame {}", frame);
+        }
+
+        if !netplay.session.should_stall() {
+            // Each peer plays with Player 1's keys, whichever side they are on
+            let local_input = {
+                let mut latched = world.resource_mut::<LatchedInputs>();
+                let input = latched.player_one;
+                latched.player_one.clear_presses();
+                input
+            };
+            netplay.session.add_local_input(local_input);
+
+            let frame = netplay.session.current_frame();
+            simulate_frame(world, &mut netplay.session, frame);
+            netplay.session.advance_frame();
+        }
+
+        // Keep sending while stalled so handshakes and acks get through
+        let message = netplay.session.outgoing_message();
+        netplay.transport.send(&message);
+        if let Some(checksum) = netplay.session.outgoing_checksum() {
+            netplay.transport.send(&checksum);
+        }
+        netplay.transport.flush();
+    });
+}
+
+/// Snapshot the world, then advance it one frame with the session's inputs
+fn simulate_frame(world: &mut World, session: &mut RollbackSession, frame: u32) {
+    session.save_snapshot(SimState::capture(world));
+
+    let (player_one, player_two) = session.inputs_for_frame(frame);
+    let mut inputs = world.resource_mut::<CurrentInputs>();
+    inputs.player_one = player_one;
+    inputs.player_two = player_two;
+
+    run_simulation_tick(world);
+}
+
+/// Run condition: false while any simulated frame still rests on a prediction
+///
+/// Keeps irreversible reactions (leaving the match for the victory screen) from
+/// acting on a frame a rollback could still undo. Always true offline.
+pub fn inputs_confirmed(netplay: Option<Res<Netplay>>) -> bool {
+    netplay.is_none_or(|netplay| netplay.session.is_confirmed())
+}
@@ -0,0 +1,396 @@
+use bevy::prelude::*;
+use crate::components::character::Player;
+use crate::components::initiative::Initiative;
+use crate::components::movelist::Movelist;
+use crate::data::game_config::GameConfig;
+use crate::data::replay::Replay;
+use crate::systems::input::CurrentInputs;
+use crate::systems::simulation::{clear_simulation_events, run_simulation_tick, SimFrame, SIMULATION_HZ};
+use crate::systems::snapshot::SimState;
+
+/// Frames between the snapshots seeking restores from (2 seconds)
+pub const SEEK_SNAPSHOT_INTERVAL: u32 = 120;
+
+/// Frames skipped by one seek key press (1 second)
+pub const SEEK_STEP: u32 = 60;
+
+/// Playback rate
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackSpeed {
+    #[default]
+    Full,
+    Half,
+    Quarter,
+}
+
+impl PlaybackSpeed {
+    /// Fixed ticks spent on each recorded frame
+    pub fn ticks_per_frame(self) -> u32 {
+        match self {
+            PlaybackSpeed::Full => 1,
+            PlaybackSpeed::Half => 2,
+            PlaybackSpeed::Quarter => 4,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlaybackSpeed::Full => "1x",
+            PlaybackSpeed::Half => "1/2x",
+            PlaybackSpeed::Quarter => "1/4x",
+        }
+    }
+}
+
+/// A recorded match being played back
+///
+/// The position is the number of recorded frames simulated so far (0 = the
+/// replay's initial state). Snapshots are taken every `SEEK_SNAPSHOT_INTERVAL`
+/// frames the first time playback reaches them, so seeking back only
+/// re-simulates from the nearest one.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub paused: bool,
+    pub speed: PlaybackSpeed,
+    /// Fixed ticks since the last frame was simulated (slow motion)
+    ticks_waited: u32,
+    /// `snapshots[i]` is the state at position `i * SEEK_SNAPSHOT_INTERVAL`
+    snapshots: Vec<SimState>,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        let initial_state = replay.header.initial_state.clone();
+        Self {
+            replay,
+            paused: false,
+            speed: PlaybackSpeed::default(),
+            ticks_waited: 0,
+            snapshots: vec![initial_state],
+        }
+    }
+
+    /// Number of recorded frames
+    pub fn frame_count(&self) -> u32 {
+        self.replay.frame_count()
+    }
+
+    /// Playback position of a simulation frame
+    pub fn position_of(&self, frame: SimFrame) -> u32 {
+        frame.0.saturating_sub(self.replay.header.initial_state.frame)
+    }
+
+    /// Recorded frames simulated so far
+    pub fn position(&self, world: &World) -> u32 {
+        self.position_of(*world.resource::<SimFrame>())
+    }
+
+    /// Restore the replay's initial state
+    pub fn restart(&mut self, world: &mut World) {
+        self.snapshots[0].restore(world);
+        clear_simulation_events(world);
+        self.ticks_waited = 0;
+    }
+
+    /// Simulate the next recorded frame, false at the end of the recording
+    pub fn step(&mut self, world: &mut World) -> bool {
+        let position = self.position(world);
+        let Some(inputs) = self.replay.inputs.get(position as usize).copied() else {
+            return false;
+        };
+
+        let next_snapshot = self.snapshots.len() as u32 * SEEK_SNAPSHOT_INTERVAL;
+        if position == next_snapshot {
+            self.snapshots.push(SimState::capture(world));
+        }
+
+        *world.resource_mut::<CurrentInputs>() = inputs;
+        run_simulation_tick(world);
+        true
+    }
+
+    /// Jump to a position, re-simulating from the closest snapshot before it
+    ///
+    /// Seeking forward keeps simulating from the current frame unless a
+    /// snapshot lies in between. The skipped frames' events are dropped, so a
+    /// seek doesn't play their hits and round ends all at once.
+    pub fn seek(&mut self, world: &mut World, target: u32) {
+        let target = target.min(self.frame_count());
+        let current = self.position(world);
+        let nearest = ((target / SEEK_SNAPSHOT_INTERVAL) as usize).min(self.snapshots.len() - 1);
+        let snapshot_position = nearest as u32 * SEEK_SNAPSHOT_INTERVAL;
+
+        if current > target || current < snapshot_position {
+            self.snapshots[nearest].restore(world);
+            clear_simulation_events(world);
+        }
+        while self.position(world) < target && self.step(world) {}
+        clear_simulation_events(world);
+        self.ticks_waited = 0;
+    }
+
+    /// Advance by one fixed tick, honouring pause and slow motion
+    pub fn tick(&mut self, world: &mut World) {
+        if self.paused {
+            return;
+        }
+
+        self.ticks_waited += 1;
+        if self.ticks_waited < self.speed.ticks_per_frame() {
+            return;
+        }
+        self.ticks_waited = 0;
+
+        if !self.step(world) {
+            self.paused = true;
+            info!("Replay: end of recording ({} frames) - Home to restart", self.frame_count());
+        }
+    }
+}
+
+/// Put the freshly spawned fighters into the replay's initial state
+pub fn start_playback(world: &mut World) {
+    world.resource_scope(|world, mut playback: Mut<ReplayPlayback>| {
+        warn_if_changed(world, &playback.replay);
+        playback.restart(world);
+        info!(
+            "Replay: {} frames, recorded with version {}",
+            playback.frame_count(),
+            playback.replay.header.game_version
+        );
+    });
+    info!("Replay controls: Space pause, . / , step frame, Left/Right seek 1s, Home restart, 1/2/3 speed 1x, 1/2x, 1/4x");
+}
+
+/// Warn when the game has changed in ways that make playback diverge
+fn warn_if_changed(world: &mut World, replay: &Replay) {
+    if replay.header.simulation_hz != SIMULATION_HZ as u32 {
+        warn!(
+            "Replay: recorded at {} Hz but the simulation runs at {} Hz - playback will diverge",
+            replay.header.simulation_hz, SIMULATION_HZ
+        );
+    }
+
+    let mut query = world.query::<(&Player, &Movelist)>();
+    for (player, movelist) in query.iter(world) {
+        let changed = replay
+            .header
+            .fighters
+            .iter()
+            .any(|fighter| fighter.player == *player && fighter.movelist_fingerprint != movelist.fingerprint());
+        if changed {
+            warn!("Replay: {:?}'s movelist has changed since recording - playback may diverge", player);
+        }
+    }
+
+    let config_changed = world
+        .get_resource::<GameConfig>()
+        .is_some_and(|config| config.fingerprint() != replay.header.config_fingerprint);
+    if config_changed {
+        warn!("Replay: the game config has changed since recording - playback may diverge");
+    }
+}
+
+/// Fixed-tick driver during playback (replaces the local driver)
+pub fn playback_tick(world: &mut World) {
+    world.resource_scope(|world, mut playback: Mut<ReplayPlayback>| playback.tick(world));
+}
+
+/// Playback controls, read once per rendered frame
+///
+/// Stepping in either direction pauses playback.
+pub fn playback_controls(world: &mut World) {
+    let keys = world.resource::<ButtonInput<KeyCode>>();
+    let toggle_pause = keys.just_pressed(KeyCode::Space);
+    let step_forward = keys.just_pressed(KeyCode::Period);
+    let step_back = keys.just_pressed(KeyCode::Comma);
+    let restart = keys.just_pressed(KeyCode::Home);
+    let seek_offset = if keys.just_pressed(KeyCode::ArrowLeft) {
+        Some(-(SEEK_STEP as i64))
+    } else if keys.just_pressed(KeyCode::ArrowRight) {
+        Some(SEEK_STEP as i64)
+    } else {
+        None
+    };
+    let speed = if keys.just_pressed(KeyCode::Digit1) {
+        Some(PlaybackSpeed::Full)
+    } else if keys.just_pressed(KeyCode::Digit2) {
+        Some(PlaybackSpeed::Half)
+    } else if keys.just_pressed(KeyCode::Digit3) {
+        Some(PlaybackSpeed::Quarter)
+    } else {
+        None
+    };
+
+    world.resource_scope(|world, mut playback: Mut<ReplayPlayback>| {
+        if toggle_pause {
+            playback.paused = !playback.paused;
+        }
+        if let Some(speed) = speed {
+            playback.speed = speed;
+            info!("Replay: speed {}", speed.label());
+        }
+
+        if step_forward {
+            playback.paused = true;
+            playback.step(world);
+        }
+        if step_back {
+            playback.paused = true;
+            let target = playback.position(world).saturating_sub(1);
+            playback.seek(world, target);
+        }
+        if let Some(offset) = seek_offset {
+            let target = (playback.position(world) as i64 + offset).max(0) as u32;
+            playback.seek(world, target);
+        }
+        if restart {
+            playback.restart(world);
+        }
+    });
+}
+
+// ==================== HUD ====================
+
+/// Marker for the playback status text
+#[derive(Component)]
+pub struct PlaybackHud;
+
+/// Spawn the playback status line at the bottom of the screen
+pub fn setup_playback_hud(mut commands: Commands) {
+    commands.spawn((
+        PlaybackHud,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 20.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    ));
+}
+
+/// Show frame, speed and both players' initiative
+pub fn update_playback_hud(
+    playback: Res<ReplayPlayback>,
+    frame: Res<SimFrame>,
+    fighters: Query<(&Player, &Initiative)>,
+    mut hud: Query<&mut Text, With<PlaybackHud>>,
+) {
+    let initiative = |player: Player| {
+        fighters
+            .iter()
+            .find(|(p, _)| **p == player)
+            .map_or(0, |(_, initiative)| initiative.frames)
+    };
+
+    let status = format!(
+        "REPLAY  frame {} / {}  {}{}    P1 initiative {:+}  P2 initiative {:+}",
+        playback.position_of(*frame),
+        playback.frame_count(),
+        playback.speed.label(),
+        if playback.paused { "  PAUSED" } else { "" },
+        initiative(Player::One),
+        initiative(Player::Two),
+    );
+
+    for mut text in hud.iter_mut() {
+        text.sections[0].value.clone_from(&status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::breath::{MatchState, RoundEndEvent, RoundEndReason};
+    use crate::data::replay::ReplayHeader;
+    use crate::systems::simulation::SimulationTick;
+
+    /// World with an empty simulation schedule - only the frame counter moves
+    fn playback_world(frames: usize) -> (World, ReplayPlayback) {
+        let mut world = World::new();
+        world.add_schedule(Schedule::new(SimulationTick));
+        world.init_resource::<SimFrame>();
+        world.init_resource::<CurrentInputs>();
+
+        let replay = Replay {
+            header: ReplayHeader {
+                game_version: "0.1.0".to_string(),
+                recorded_at: 0,
+                simulation_hz: 60,
+                fighters: Vec::new(),
+                config_fingerprint: 0,
+                seed: 0,
+                winner: None,
+                initial_state: SimState {
+                    frame: 0,
+                    match_state: MatchState::default(),
+                    winner: None,
+                    fighters: Vec::new(),
+                },
+            },
+            inputs: vec![CurrentInputs::default(); frames],
+        };
+        (world, ReplayPlayback::new(replay))
+    }
+
+    #[test]
+    fn test_seek_back_and_forward() {
+        let (mut world, mut playback) = playback_world(500);
+
+        playback.seek(&mut world, 300);
+        assert_eq!(playback.position(&world), 300);
+        // Snapshots at 0, 120 and 240
+        assert_eq!(playback.snapshots.len(), 3);
+
+        playback.seek(&mut world, 130);
+        assert_eq!(playback.position(&world), 130);
+
+        playback.seek(&mut world, 10_000);
+        assert_eq!(playback.position(&world), 500);
+        assert!(!playback.step(&mut world));
+    }
+
+    #[test]
+    fn test_seek_drops_skipped_events() {
+        let (mut world, mut playback) = playback_world(300);
+        world.init_resource::<Events<RoundEndEvent>>();
+        world.schedule_scope(SimulationTick, |_, schedule| {
+            schedule.add_systems(|mut round_ends: EventWriter<RoundEndEvent>| {
+                round_ends.send(RoundEndEvent { winner: Entity::PLACEHOLDER, reason: RoundEndReason::Timeout });
+            });
+        });
+        let mut presentation = world.resource::<Events<RoundEndEvent>>().get_reader();
+
+        playback.seek(&mut world, 60);
+        assert_eq!(presentation.read(world.resource::<Events<RoundEndEvent>>()).count(), 0);
+        playback.seek(&mut world, 30);
+        assert_eq!(presentation.read(world.resource::<Events<RoundEndEvent>>()).count(), 0);
+
+        // Playing on shows each frame's events again
+        playback.step(&mut world);
+        assert_eq!(presentation.read(world.resource::<Events<RoundEndEvent>>()).count(), 1);
+    }
+
+    #[test]
+    fn test_slow_motion_and_pause() {
+        let (mut world, mut playback) = playback_world(10);
+        playback.speed = PlaybackSpeed::Quarter;
+
+        for _ in 0..8 {
+            playback.tick(&mut world);
+        }
+        assert_eq!(playback.position(&world), 2);
+
+        playback.paused = true;
+        playback.tick(&mut world);
+        assert_eq!(playback.position(&world), 2);
+    }
+}
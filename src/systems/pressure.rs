@@ -1,19 +1,23 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::components::character::{MaxSpeed, Player};
 use crate::components::initiative::Initiative;
+use crate::data::game_config::GameConfig;
 use crate::events::combat_events::HitEvent;
 
 /// Pressure state - tracks offensive momentum
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Pressure {
     /// Intensity level (0-3)
     /// 0 = None, 1 = Light, 2 = Medium, 3 = Heavy
     pub intensity: u8,
+    /// Frames spent idle since pressure last drained
+    pub idle_frames: u32,
 }
 
 impl Pressure {
     pub fn new() -> Self {
-        Self { intensity: 0 }
+        Self { intensity: 0, idle_frames: 0 }
     }
 
     /// Increase pressure (max 3)
@@ -66,6 +70,10 @@ pub fn build_pressure(
     mut query: Query<(&Initiative, &mut Pressure, &Player)>,
 ) {
     for event in hit_events.read() {
+        if event.parried {
+            continue;
+        }
+
         // Attacker builds pressure if they land a hit
         if let Ok((initiative, mut pressure, player)) = query.get_mut(event.attacker) {
             // Only build pressure if:
@@ -94,31 +102,30 @@ pub fn build_pressure(
 
 /// Apply pressure bonuses to movement speed
 pub fn apply_pressure_movement_bonus(
+    config: Res<GameConfig>,
     mut query: Query<(&Pressure, &mut MaxSpeed), Changed<Pressure>>,
 ) {
     for (pressure, mut max_speed) in query.iter_mut() {
-        // Base speed is 300.0, apply multiplier
-        let base_speed = 300.0;
-        max_speed.0 = base_speed * pressure.movement_bonus();
+        // Apply multiplier to base walk speed
+        max_speed.0 = config.walk_speed * pressure.movement_bonus();
     }
 }
 
 /// Gradually drain pressure when not actively attacking
 pub fn drain_pressure_passive(
-    time: Res<Time>,
+    config: Res<GameConfig>,
     mut query: Query<(&mut Pressure, &crate::components::state::CharacterState)>,
 ) {
     for (mut pressure, state) in query.iter_mut() {
         // Only drain when idle (not actively fighting)
         if matches!(state, crate::components::state::CharacterState::Idle) {
-            // Drain slowly over time (every ~2 seconds at 60fps)
-            // Using a simple frame counter approach
-            static mut FRAME_COUNTER: u32 = 0;
-            unsafe {
-                FRAME_COUNTER += 1;
-                if FRAME_COUNTER >= 120 && pressure.intensity > 0 {
+            // Drain slowly over time (every pressure_drain_frames)
+            // Counter lives on the component so it is part of the match snapshot
+            pressure.idle_frames += 1;
+            if pressure.idle_frames >= config.pressure_drain_frames {
+                pressure.idle_frames = 0;
+                if pressure.intensity > 0 {
                     pressure.decrease();
-                    FRAME_COUNTER = 0;
                     debug!("Pressure drained (idle), now at level {}", pressure.intensity);
                 }
             }
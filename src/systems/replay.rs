@@ -0,0 +1,180 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use bevy::prelude::*;
+use crate::components::character::Player;
+use crate::components::movelist::{Movelist, DEFAULT_CHARACTER};
+use crate::data::game_config::GameConfig;
+use crate::data::replay::{Replay, ReplayFighter, ReplayHeader};
+use crate::systems::input::CurrentInputs;
+use crate::systems::simulation::{MatchSeed, SimFrame, SIMULATION_HZ};
+use crate::systems::snapshot::SimState;
+
+/// Inputs of the match in progress, written to disk when it ends
+///
+/// Data hot-reloaded into the match splits the recording: the segment played
+/// with the old data is set aside and a new one starts at the next frame, so
+/// each replay file plays back with a single version of the movelists and
+/// config.
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    recording: bool,
+    initial_state: Option<SimState>,
+    /// Data the segment is played with, taken along with `initial_state`
+    fighters: Vec<ReplayFighter>,
+    config_fingerprint: u64,
+    inputs: Vec<CurrentInputs>,
+    /// Data was swapped in: the next frame starts a new segment
+    split_pending: bool,
+    /// Earlier segments of this match, saved with the last one
+    finished: Vec<Replay>,
+}
+
+impl ReplayRecorder {
+    /// End the segment being recorded; the next frame starts a new one
+    ///
+    /// Called when reloaded data is swapped into the match. Does nothing
+    /// before the first frame is recorded.
+    pub fn split_segment(&mut self) {
+        if self.initial_state.is_some() {
+            self.split_pending = true;
+        }
+    }
+
+    /// Turn the segment being recorded into a replay, `None` if it is empty
+    fn take_segment(&mut self, world: &mut World) -> Option<Replay> {
+        let initial_state = self.initial_state.take()?;
+        let inputs = std::mem::take(&mut self.inputs);
+        if inputs.is_empty() {
+            return None;
+        }
+
+        let recorded_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0);
+
+        Some(Replay {
+            header: ReplayHeader {
+                game_version: env!("CARGO_PKG_VERSION").to_string(),
+                recorded_at,
+                simulation_hz: SIMULATION_HZ as u32,
+                fighters: std::mem::take(&mut self.fighters),
+                config_fingerprint: self.config_fingerprint,
+                seed: world.get_resource::<MatchSeed>().map_or(0, |seed| seed.0),
+                winner: SimState::capture(world).winner,
+                initial_state,
+            },
+            inputs,
+        })
+    }
+}
+
+/// Start recording when a match starts
+pub fn start_recording(mut recorder: ResMut<ReplayRecorder>) {
+    *recorder = ReplayRecorder {
+        recording: true,
+        ..default()
+    };
+}
+
+/// Record the inputs of the frame about to be simulated
+///
+/// Called from `run_simulation_tick`, so every driver is covered. Inputs are
+/// stored by frame: when a rollback or save state re-runs earlier frames, the
+/// recording is cut back and rewritten, leaving exactly the inputs that
+/// produced the final match.
+pub fn record_frame(world: &mut World) {
+    let frame = world.resource::<SimFrame>().0;
+    let inputs = *world.resource::<CurrentInputs>();
+
+    if !world.get_resource::<ReplayRecorder>().is_some_and(|recorder| recorder.recording) {
+        return;
+    }
+
+    world.resource_scope(|world, mut recorder: Mut<ReplayRecorder>| {
+        if recorder.split_pending {
+            recorder.split_pending = false;
+            if let Some(segment) = recorder.take_segment(world) {
+                recorder.finished.push(segment);
+            }
+        }
+
+        // The first frame recorded starts the segment
+        if recorder.initial_state.is_none() {
+            recorder.initial_state = Some(SimState::capture(world));
+            recorder.fighters = replay_fighters(world);
+            recorder.config_fingerprint = world.get_resource::<GameConfig>().map_or(0, GameConfig::fingerprint);
+        }
+
+        let start = recorder.initial_state.as_ref().map_or(frame, |state| state.frame);
+        let Some(index) = frame.checked_sub(start).map(|index| index as usize) else {
+            return;
+        };
+        if index > recorder.inputs.len() {
+            return;
+        }
+        recorder.inputs.truncate(index);
+        recorder.inputs.push(inputs);
+    });
+}
+
+/// Write the finished (or abandoned) match to the replays directory, one file
+/// per segment
+///
+/// Runs on leaving `InGame`, before the fighters are despawned.
+pub fn save_replay(world: &mut World) {
+    let mut recorder = std::mem::take(&mut *world.resource_mut::<ReplayRecorder>());
+    let last = recorder.take_segment(world);
+
+    for replay in recorder.finished.into_iter().chain(last) {
+        match replay.save() {
+            Ok(path) => info!("Replay saved: {} ({} frames)", path.display(), replay.frame_count()),
+            Err(e) => warn!("Failed to save replay: {}", e),
+        }
+    }
+}
+
+/// Each fighter's character and movelist fingerprint, Player One first
+fn replay_fighters(world: &mut World) -> Vec<ReplayFighter> {
+    let mut fighters: Vec<ReplayFighter> = world
+        .query::<(&Player, &Movelist)>()
+        .iter(world)
+        .map(|(player, movelist)| ReplayFighter {
+            player: *player,
+            character: DEFAULT_CHARACTER.to_string(),
+            movelist_fingerprint: movelist.fingerprint(),
+        })
+        .collect();
+    fighters.sort_by_key(|fighter| fighter.player != Player::One);
+    fighters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::simulation::{run_simulation_tick, SimulationTick};
+
+    #[test]
+    fn test_reload_splits_recording() {
+        let mut world = World::new();
+        world.add_schedule(Schedule::new(SimulationTick));
+        world.init_resource::<SimFrame>();
+        world.init_resource::<CurrentInputs>();
+        world.insert_resource(ReplayRecorder { recording: true, ..default() });
+
+        for _ in 0..3 {
+            run_simulation_tick(&mut world);
+        }
+        world.resource_mut::<ReplayRecorder>().split_segment();
+        for _ in 0..2 {
+            run_simulation_tick(&mut world);
+        }
+
+        let recorder = world.resource::<ReplayRecorder>();
+        assert_eq!(recorder.finished.len(), 1);
+        assert_eq!(recorder.finished[0].header.initial_state.frame, 0);
+        assert_eq!(recorder.finished[0].frame_count(), 3);
+        // The new segment picks up where the old one stopped
+        assert_eq!(recorder.initial_state.as_ref().map(|state| state.frame), Some(3));
+        assert_eq!(recorder.inputs.len(), 2);
+    }
+}
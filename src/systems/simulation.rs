@@ -0,0 +1,97 @@
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::breath::RoundEndEvent;
+use crate::events::combat_events::{GrabEvent, GuardBreakEvent, HitEvent, ParryEvent};
+use crate::systems::decisive::{DecisiveCutEvent, FinalParryEvent};
+use crate::systems::input::LatchedInputs;
+use crate::systems::replay;
+use crate::systems::tenuki::TenukiEvent;
+
+/// Simulation rate - every gameplay rule advances exactly one frame per tick
+pub const SIMULATION_HZ: f64 = 60.0;
+
+/// Length of one simulation frame in seconds (for values tuned "per second")
+pub const FRAME_SECONDS: f32 = 1.0 / SIMULATION_HZ as f32;
+
+/// Schedule containing every gameplay rule
+///
+/// Run once per fixed tick from `FixedUpdate`, never from `Update`, so frame
+/// data means frames regardless of the render rate.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationTick;
+
+/// Ordered stages of a simulation tick
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimSet {
+    /// Buffer inputs and start new actions
+    Input,
+    /// Hitstop, timers and state machine progression
+    Progression,
+    /// Movement, stage bounds and hit detection
+    Physics,
+    /// Responses to hits: stumble, parry, damage, round end
+    Reactions,
+    /// Meter and advantage bookkeeping after reactions resolve
+    Bookkeeping,
+}
+
+/// Number of simulation frames run since the match started
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SimFrame(pub u32);
+
+/// Seed for randomness in the simulation
+///
+/// Gameplay has no random elements yet; the seed is part of a match's initial
+/// conditions so replays stay valid once it does.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchSeed(pub u64);
+
+/// What feeds inputs into the simulation and advances it each fixed tick
+///
+/// `Local` reads the keyboard for both players. Other drivers (netplay, replay
+/// playback) take over `FixedUpdate` and call `run_simulation_tick` themselves.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationDriver {
+    #[default]
+    Local,
+    Netplay,
+    Replay,
+}
+
+/// Advance the match by exactly one simulation frame
+pub fn run_simulation_tick(world: &mut World) {
+    replay::record_frame(world);
+    world.run_schedule(SimulationTick);
+    world.resource_mut::<SimFrame>().0 += 1;
+}
+
+/// Drop every event the simulation has sent so far
+///
+/// For re-simulated ticks (rollback, replay seeking, sync test): presentation
+/// already showed their hits and round ends, or never should. The
+/// simulation's own readers have read them by then.
+pub fn clear_simulation_events(world: &mut World) {
+    fn clear<E: Event>(world: &mut World) {
+        if let Some(mut events) = world.get_resource_mut::<Events<E>>() {
+            events.clear();
+        }
+    }
+    clear::<HitEvent>(world);
+    clear::<ParryEvent>(world);
+    clear::<GuardBreakEvent>(world);
+    clear::<GrabEvent>(world);
+    clear::<TenukiEvent>(world);
+    clear::<FinalParryEvent>(world);
+    clear::<DecisiveCutEvent>(world);
+    clear::<RoundEndEvent>(world);
+}
+
+/// Reset frame counter and pending inputs when a match starts
+pub fn reset_simulation(
+    mut frame: ResMut<SimFrame>,
+    mut latched: ResMut<LatchedInputs>,
+) {
+    frame.0 = 0;
+    *latched = LatchedInputs::default();
+}
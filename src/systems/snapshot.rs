@@ -0,0 +1,372 @@
+use bevy::ecs::world::EntityWorldMut;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::breath::{Breath, Desperation, MatchState};
+use crate::components::character::{Character, Corner, Facing, MaxSpeed, Player, Velocity};
+use crate::components::combat::{Hitbox, Hurtbox, Pushbox};
+use crate::components::combo::InputBuffer;
+use crate::components::guard::{GuardMeter, PunishWindow};
+use crate::components::health::{FinalStand, Health};
+use crate::components::hitstop::Hitstop;
+use crate::components::initiative::Initiative;
+use crate::components::movelist::DEFAULT_CHARACTER;
+use crate::components::state::{CharacterState, StateTimer};
+use crate::components::stumble::StumbleState;
+use crate::data::character::Characters;
+use crate::systems::chain::ChainState;
+use crate::systems::decisive::Slain;
+use crate::systems::evade::EvadeData;
+use crate::systems::momentum::Momentum;
+use crate::systems::movement::{ActiveAttackMovement, DashCooldown, DashData};
+use crate::systems::pressure::Pressure;
+use crate::systems::simulation::SimFrame;
+use crate::systems::stumble::{SpikeFlash, TechFlash, WallBounceFlash};
+use crate::systems::corner::Pushback;
+use crate::systems::jump::JumpArc;
+use crate::systems::tenuki::Tenuki;
+
+/// Complete gameplay state of one fighter
+///
+/// Optional fields mirror components that are inserted and removed during play
+/// (timers, stumble, dash, evade, visual markers). `None` means the component
+/// is absent and will be removed on restore.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FighterSnapshot {
+    pub player: Player,
+    pub position: Vec3,
+    pub facing: Facing,
+    pub velocity: Velocity,
+    pub max_speed: MaxSpeed,
+    pub state: CharacterState,
+    pub hurtbox: Hurtbox,
+    pub pushbox: Pushbox,
+    pub guard: GuardMeter,
+    pub initiative: Initiative,
+    pub pressure: Pressure,
+    pub momentum: Momentum,
+    pub chain: ChainState,
+    pub input_buffer: InputBuffer,
+    pub health: Health,
+    pub breath: Breath,
+
+    pub state_timer: Option<StateTimer>,
+    pub hitstop: Option<Hitstop>,
+    pub hitbox: Option<Hitbox>,
+    pub stumble: Option<StumbleState>,
+    pub evade: Option<EvadeData>,
+    pub dash: Option<DashData>,
+    pub dash_cooldown: Option<DashCooldown>,
+    pub attack_movement: Option<ActiveAttackMovement>,
+    pub tech_flash: Option<TechFlash>,
+    pub wall_bounce_flash: Option<WallBounceFlash>,
+    pub spike_flash: Option<SpikeFlash>,
+    pub tenuki: Option<Tenuki>,
+    pub jump_arc: Option<JumpArc>,
+    pub corner: Option<Corner>,
+    pub pushback: Option<Pushback>,
+    pub desperation: Option<Desperation>,
+    pub final_stand: Option<FinalStand>,
+    pub punish_window: Option<PunishWindow>,
+    pub slain: Option<Slain>,
+}
+
+impl FighterSnapshot {
+    /// Read a fighter's state, or `None` if the entity is missing required components
+    pub fn capture(entity: EntityRef) -> Option<Self> {
+        Some(Self {
+            player: *entity.get::<Player>()?,
+            position: entity.get::<Transform>()?.translation,
+            facing: *entity.get::<Facing>()?,
+            velocity: entity.get::<Velocity>()?.clone(),
+            max_speed: entity.get::<MaxSpeed>()?.clone(),
+            state: entity.get::<CharacterState>()?.clone(),
+            hurtbox: entity.get::<Hurtbox>()?.clone(),
+            pushbox: entity.get::<Pushbox>()?.clone(),
+            guard: entity.get::<GuardMeter>()?.clone(),
+            initiative: entity.get::<Initiative>()?.clone(),
+            pressure: entity.get::<Pressure>()?.clone(),
+            momentum: entity.get::<Momentum>()?.clone(),
+            chain: entity.get::<ChainState>()?.clone(),
+            input_buffer: entity.get::<InputBuffer>()?.clone(),
+            health: entity.get::<Health>()?.clone(),
+            breath: entity.get::<Breath>()?.clone(),
+
+            state_timer: entity.get::<StateTimer>().cloned(),
+            hitstop: entity.get::<Hitstop>().cloned(),
+            hitbox: entity.get::<Hitbox>().cloned(),
+            stumble: entity.get::<StumbleState>().cloned(),
+            evade: entity.get::<EvadeData>().cloned(),
+            dash: entity.get::<DashData>().cloned(),
+            dash_cooldown: entity.get::<DashCooldown>().cloned(),
+            attack_movement: entity.get::<ActiveAttackMovement>().cloned(),
+            tech_flash: entity.get::<TechFlash>().cloned(),
+            wall_bounce_flash: entity.get::<WallBounceFlash>().cloned(),
+            spike_flash: entity.get::<SpikeFlash>().cloned(),
+            tenuki: entity.get::<Tenuki>().cloned(),
+            jump_arc: entity.get::<JumpArc>().cloned(),
+            corner: entity.get::<Corner>().cloned(),
+            pushback: entity.get::<Pushback>().cloned(),
+            desperation: entity.get::<Desperation>().cloned(),
+            final_stand: entity.get::<FinalStand>().cloned(),
+            punish_window: entity.get::<PunishWindow>().cloned(),
+            slain: entity.get::<Slain>().cloned(),
+        })
+    }
+
+    /// Overwrite a fighter entity with this snapshot
+    pub fn apply(&self, entity: &mut EntityWorldMut) {
+        // Keep rotation/scale, only position is gameplay state
+        match entity.get_mut::<Transform>() {
+            Some(mut transform) => transform.translation = self.position,
+            None => {
+                entity.insert(TransformBundle::from_transform(Transform::from_translation(self.position)));
+            }
+        }
+
+        entity.insert((
+            self.player,
+            self.facing,
+            self.velocity.clone(),
+            self.max_speed.clone(),
+            self.state.clone(),
+            self.hurtbox.clone(),
+            self.pushbox.clone(),
+            self.guard.clone(),
+            self.initiative.clone(),
+            self.pressure.clone(),
+            self.momentum.clone(),
+            self.chain.clone(),
+            self.input_buffer.clone(),
+            self.health.clone(),
+            self.breath.clone(),
+        ));
+
+        insert_or_remove(entity, &self.state_timer);
+        insert_or_remove(entity, &self.hitstop);
+        insert_or_remove(entity, &self.hitbox);
+        insert_or_remove(entity, &self.stumble);
+        insert_or_remove(entity, &self.evade);
+        insert_or_remove(entity, &self.dash);
+        insert_or_remove(entity, &self.dash_cooldown);
+        insert_or_remove(entity, &self.attack_movement);
+        insert_or_remove(entity, &self.tech_flash);
+        insert_or_remove(entity, &self.wall_bounce_flash);
+        insert_or_remove(entity, &self.spike_flash);
+        insert_or_remove(entity, &self.tenuki);
+        insert_or_remove(entity, &self.jump_arc);
+        insert_or_remove(entity, &self.corner);
+        insert_or_remove(entity, &self.pushback);
+        insert_or_remove(entity, &self.desperation);
+        insert_or_remove(entity, &self.final_stand);
+        insert_or_remove(entity, &self.punish_window);
+        insert_or_remove(entity, &self.slain);
+    }
+}
+
+/// Insert an optional component, or remove it when the snapshot has none
+fn insert_or_remove<T: Component + Clone>(entity: &mut EntityWorldMut, value: &Option<T>) {
+    match value {
+        Some(component) => {
+            entity.insert(component.clone());
+        }
+        None => {
+            entity.remove::<T>();
+        }
+    }
+}
+
+/// Deterministic snapshot of a whole match
+///
+/// Captures both fighters and the `MatchState` resource at a simulation frame.
+/// Restoring it and running the same inputs reproduces the same match, which is
+/// the basis for save states, replays and rollback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimState {
+    /// Simulation frame this snapshot was taken at
+    pub frame: u32,
+    /// Round/timer state (winner stored separately as a `Player`)
+    pub match_state: MatchState,
+    /// Match winner, if the match is over
+    pub winner: Option<Player>,
+    /// Fighters ordered Player One, Player Two
+    pub fighters: Vec<FighterSnapshot>,
+}
+
+impl SimState {
+    /// Capture the current match from the world
+    pub fn capture(world: &mut World) -> Self {
+        let mut query = world.query_filtered::<Entity, With<Character>>();
+        let entities: Vec<Entity> = query.iter(world).collect();
+
+        let mut fighters: Vec<FighterSnapshot> = entities
+            .into_iter()
+            .filter_map(|entity| FighterSnapshot::capture(world.entity(entity)))
+            .collect();
+        fighters.sort_by_key(|fighter| player_index(fighter.player));
+
+        let match_state = world
+            .get_resource::<MatchState>()
+            .cloned()
+            .unwrap_or_default();
+        let winner = match_state
+            .winner
+            .and_then(|entity| world.get::<Player>(entity).copied());
+        let frame = world.get_resource::<SimFrame>().map(|f| f.0).unwrap_or(0);
+
+        Self {
+            frame,
+            match_state,
+            winner,
+            fighters,
+        }
+    }
+
+    /// Restore this snapshot into the world
+    ///
+    /// Existing fighter entities are overwritten in place (so entity ids held by
+    /// other systems stay valid); a missing fighter is respawned.
+    pub fn restore(&self, world: &mut World) {
+        for fighter in &self.fighters {
+            let entity = match find_fighter(world, fighter.player) {
+                Some(entity) => entity,
+                None => {
+                    let movelist = world
+                        .get_resource::<Characters>()
+                        .and_then(|characters| characters.movelist(DEFAULT_CHARACTER))
+                        .cloned()
+                        .unwrap_or_default();
+                    world.spawn((Character, movelist)).id()
+                }
+            };
+            fighter.apply(&mut world.entity_mut(entity));
+        }
+
+        let mut match_state = self.match_state.clone();
+        match_state.winner = self.winner.and_then(|player| find_fighter(world, player));
+        world.insert_resource(match_state);
+        world.insert_resource(SimFrame(self.frame));
+    }
+
+    /// Hash of the serialized state
+    ///
+    /// Covers everything the snapshot holds (positions, states, timers, meters,
+    /// health, breath, match state), so two runs agree on the checksum exactly
+    /// when they agree on the match.
+    pub fn checksum(&self) -> u64 {
+        let serialized = ron::to_string(self).expect("SimState is always serializable");
+        fnv1a(serialized.as_bytes())
+    }
+}
+
+/// 64-bit FNV-1a hash - stable across runs and platforms, unlike `DefaultHasher`
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Find the fighter entity controlled by a player
+pub fn find_fighter(world: &mut World, player: Player) -> Option<Entity> {
+    let mut query = world.query_filtered::<(Entity, &Player), With<Character>>();
+    query
+        .iter(world)
+        .find(|(_, p)| **p == player)
+        .map(|(entity, _)| entity)
+}
+
+fn player_index(player: Player) -> u8 {
+    match player {
+        Player::One => 0,
+        Player::Two => 1,
+    }
+}
+
+// ==================== TRAINING SAVE STATE ====================
+
+/// Training-mode save state slot
+#[derive(Resource, Default)]
+pub struct SaveStateSlot(pub Option<SimState>);
+
+/// F5 saves the current match state, F6 restores it
+pub fn save_state_hotkeys(world: &mut World) {
+    let keys = world.resource::<ButtonInput<KeyCode>>();
+    let save = keys.just_pressed(KeyCode::F5);
+    let load = keys.just_pressed(KeyCode::F6);
+
+    if save {
+        let state = SimState::capture(world);
+        info!("Save state captured at frame {}", state.frame);
+        world.resource_mut::<SaveStateSlot>().0 = Some(state);
+    }
+
+    if load {
+        if let Some(state) = world.resource::<SaveStateSlot>().0.clone() {
+            state.restore(world);
+            info!("Save state restored (frame {})", state.frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::state::AttackType;
+    use crate::components::movelist::AttackDirection;
+    use crate::components::state::AttackPhase;
+
+    fn spawn_fighter(world: &mut World, player: Player, x: f32) -> Entity {
+        world
+            .spawn((
+                Character,
+                player,
+                TransformBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+                Velocity::default(),
+                MaxSpeed(300.0),
+                CharacterState::Idle,
+                Hurtbox::default(),
+                GuardMeter::default(),
+                Initiative::default(),
+                Pressure::default(),
+                Momentum::default(),
+                ChainState::default(),
+                Health::default(),
+                Breath::default(),
+            ))
+            .insert((InputBuffer::default(), Facing::towards(x, 0.0), Pushbox::default()))
+            .id()
+    }
+
+    #[test]
+    fn test_restore_round_trip() {
+        let mut world = World::new();
+        world.insert_resource(MatchState::default());
+        world.insert_resource(SimFrame(42));
+        let p1 = spawn_fighter(&mut world, Player::One, -300.0);
+        spawn_fighter(&mut world, Player::Two, 300.0);
+
+        let saved = SimState::capture(&mut world);
+        let saved_ron = ron::to_string(&saved).unwrap();
+
+        // Mutate the match: move, attack, take damage, add optional components
+        {
+            let mut fighter = world.entity_mut(p1);
+            fighter.get_mut::<Transform>().unwrap().translation.x = 120.0;
+            *fighter.get_mut::<CharacterState>().unwrap() = CharacterState::Attacking {
+                attack_type: AttackType::Heavy,
+                direction: AttackDirection::Neutral,
+                phase: AttackPhase::Startup,
+            };
+            fighter.get_mut::<Health>().unwrap().take_damage(30.0);
+            fighter.insert((StateTimer::new(11), Hitstop::new(9)));
+        }
+        world.resource_mut::<SimFrame>().0 = 90;
+
+        saved.restore(&mut world);
+        let restored = SimState::capture(&mut world);
+
+        assert_eq!(ron::to_string(&restored).unwrap(), saved_ron);
+        assert!(world.get::<StateTimer>(p1).is_none());
+        assert!(world.get::<Hitstop>(p1).is_none());
+        assert_eq!(world.resource::<SimFrame>().0, 42);
+    }
+}
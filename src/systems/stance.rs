@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use crate::components::character::{Facing, MaxSpeed, Player, Velocity};
+use crate::components::combat::Hitbox;
+use crate::components::guard::GuardMeter;
+use crate::components::initiative::Initiative;
+use crate::components::movelist::{AttackDirection, Movelist};
+use crate::components::state::{AttackPhase, CharacterState, StateTimer};
+use crate::systems::attack::create_hitbox;
+use crate::systems::input::CurrentInputs;
+use crate::systems::simulation::FRAME_SECONDS;
+
+/// Enter, hold, release and cancel stances (Aji)
+///
+/// An attack whose button is still held `entry_frames` into its startup
+/// becomes that button's stance, if the character has one. In the stance,
+/// letting go of the button releases the stance move, Block cancels back to
+/// neutral for the stance's `cancel_cost` in initiative, and a stance with
+/// `walk_speed_multiplier` can creep. Runs after `attack::handle_attack_input`
+/// so the release isn't mistaken for a fresh attack.
+#[allow(clippy::type_complexity)]
+pub fn handle_stance_input(
+    mut commands: Commands,
+    inputs: Res<CurrentInputs>,
+    mut query: Query<(
+        Entity,
+        &Player,
+        &mut CharacterState,
+        Option<&StateTimer>,
+        &Movelist,
+        &mut Initiative,
+        &mut Velocity,
+        &MaxSpeed,
+    )>,
+) {
+    for (entity, player, mut state, timer, movelist, mut initiative, mut velocity, max_speed) in query.iter_mut() {
+        let input = match player {
+            Player::One => &inputs.player_one,
+            Player::Two => &inputs.player_two,
+        };
+
+        match *state {
+            CharacterState::Attacking { attack_type, direction, phase: AttackPhase::Startup }
+                if !matches!(direction, AttackDirection::Air | AttackDirection::Stance) =>
+            {
+                let Some(stance) = movelist.stance(attack_type) else {
+                    continue;
+                };
+                let held_long_enough = timer.is_some_and(|timer| timer.elapsed >= stance.entry_frames);
+                if input.is_held(attack_type) && held_long_enough {
+                    // The startup never reaches its active frames
+                    *state = CharacterState::Stance { button: attack_type };
+                    commands.entity(entity).remove::<(StateTimer, Hitbox)>();
+                    info!("Player {:?} enters {}", player, stance.name);
+                }
+            }
+            CharacterState::Stance { button } => {
+                // Only possible if a reload took the stance away
+                let Some(stance) = movelist.stance(button) else {
+                    *state = CharacterState::Idle;
+                    continue;
+                };
+
+                if input.block {
+                    *state = CharacterState::Idle;
+                    velocity.0.x = 0.0;
+                    initiative.lose(stance.cancel_cost);
+                    info!("Player {:?} cancels {} (-{}f)", player, stance.name, stance.cancel_cost);
+                } else if !input.is_held(button) {
+                    velocity.0.x = 0.0;
+                    let release = movelist
+                        .resolve(button, AttackDirection::Stance)
+                        .and_then(|move_id| movelist.get(move_id));
+                    let Some(move_data) = release else {
+                        *state = CharacterState::Idle;
+                        continue;
+                    };
+
+                    *state = CharacterState::Attacking {
+                        attack_type: button,
+                        direction: AttackDirection::Stance,
+                        phase: AttackPhase::Startup,
+                    };
+                    commands.entity(entity).insert((StateTimer::new(move_data.startup_frames), create_hitbox(move_data)));
+                    info!("Player {:?} releases {}: {}", player, stance.name, move_data.name);
+                } else {
+                    velocity.0.x = input.movement.x * max_speed.0 * stance.walk_speed_multiplier;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Holding a stance fills the guard meter; it can't be held forever
+///
+/// (`guard::drain_guard_meter` doesn't recover guard meanwhile.)
+pub fn drain_guard_in_stance(
+    mut query: Query<(&CharacterState, &Movelist, &mut GuardMeter)>,
+) {
+    for (state, movelist, mut guard) in query.iter_mut() {
+        let CharacterState::Stance { button } = state else {
+            continue;
+        };
+        if let Some(stance) = movelist.stance(*button) {
+            guard.fill(stance.guard_drain * FRAME_SECONDS);
+        }
+    }
+}
+
+/// The held blade: a line out in front of a fighter in stance
+pub fn visualize_stance(
+    mut gizmos: Gizmos,
+    query: Query<(&CharacterState, &Transform, &Facing)>,
+) {
+    for (state, transform, facing) in query.iter() {
+        if !matches!(state, CharacterState::Stance { .. }) {
+            continue;
+        }
+        let pos = transform.translation.xy();
+        let sign = facing.sign();
+
+        gizmos.line_2d(
+            pos + Vec2::new(20.0 * sign, 10.0),
+            pos + Vec2::new(110.0 * sign, 30.0),
+            Color::srgba(0.95, 0.95, 1.0, 0.9),
+        );
+        gizmos.rect_2d(pos, 0.0, Vec2::new(112.0, 212.0), Color::srgba(0.85, 0.85, 1.0, 0.35));
+    }
+}
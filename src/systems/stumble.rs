@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use crate::components::character::Player;
+use serde::{Deserialize, Serialize};
+use crate::components::character::{Corner, Facing, Player};
 use crate::components::initiative::Initiative;
 use crate::components::movelist::{AttackDirection, Movelist};
 use crate::components::state::{AttackPhase, CharacterState};
@@ -18,8 +19,8 @@ pub fn apply_stumble_on_hit(
     defender_query: Query<&StumbleState>,
 ) {
     for event in hit_events.read() {
-        // Only unblocked hits cause stumble
-        if event.was_blocked {
+        // Only unblocked hits cause stumble; an absorbing Heavy keeps its footing
+        if event.was_blocked || event.absorbed || event.parried {
             continue;
         }
 
@@ -164,37 +165,36 @@ pub fn handle_tech_input(
 }
 
 /// Marker component for tech flash visual effect
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct TechFlash {
     pub frames_remaining: u8,
 }
 
 // ==================== WALL BOUNCE ====================
 
-/// Stage boundaries
-const STAGE_HALF_WIDTH: f32 = 500.0;
-const WALL_BOUNCE_THRESHOLD: f32 = STAGE_HALF_WIDTH - 30.0; // Account for character width
-
 /// Detect wall bounce during stumble
 ///
-/// When a stumbling player hits the stage boundary:
+/// Stumble directions are relative to the stumbling fighter's facing
+/// (backward = away from the way it faces). When a stumbling player is
+/// carried into the corner (see `Corner`):
 /// - Direction reverses
 /// - +20 frames added to stumble
 /// - Cannot tech during bounce
 pub fn detect_wall_bounce(
     mut commands: Commands,
-    mut query: Query<(Entity, &Transform, &mut StumbleState)>,
+    mut query: Query<(Entity, &Transform, &Facing, &Corner, &mut StumbleState)>,
 ) {
-    for (entity, transform, mut stumble) in query.iter_mut() {
+    for (entity, transform, facing, corner, mut stumble) in query.iter_mut() {
         let x = transform.translation.x;
 
-        // Check if hit left or right wall
-        let hit_left_wall = x <= -WALL_BOUNCE_THRESHOLD
-            && matches!(stumble.direction, StumbleDirection::Backward);
-        let hit_right_wall = x >= WALL_BOUNCE_THRESHOLD
-            && matches!(stumble.direction, StumbleDirection::Forward);
+        // World-space direction of the stumble (Down never bounces)
+        let heading = match stumble.direction {
+            StumbleDirection::Backward => -facing.sign(),
+            StumbleDirection::Forward => facing.sign(),
+            StumbleDirection::Down => 0.0,
+        };
 
-        if hit_left_wall || hit_right_wall {
+        if corner.into_wall(heading) {
             // Apply wall bounce effect
             stumble.apply_wall_bounce();
 
@@ -210,7 +210,7 @@ pub fn detect_wall_bounce(
 }
 
 /// Marker component for wall bounce visual effect
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct WallBounceFlash {
     pub frames_remaining: u8,
 }
@@ -288,7 +288,7 @@ pub fn handle_spike_finisher(
 }
 
 /// Marker component for spike finisher visual effect
-#[derive(Component)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct SpikeFlash {
     pub frames_remaining: u8,
 }
@@ -315,14 +315,47 @@ pub fn process_stumble(
     }
 }
 
+/// Count down tech, wall bounce and spike flashes, removing them when done
+///
+/// The flash markers are part of `SimState`, so they change only here; the
+/// visual effects below just read them.
+pub fn tick_impact_flashes(
+    mut commands: Commands,
+    mut tech_flashes: Query<(Entity, &mut TechFlash)>,
+    mut wall_bounce_flashes: Query<(Entity, &mut WallBounceFlash)>,
+    mut spike_flashes: Query<(Entity, &mut SpikeFlash)>,
+) {
+    for (entity, mut flash) in tech_flashes.iter_mut() {
+        if flash.frames_remaining > 0 {
+            flash.frames_remaining -= 1;
+        } else {
+            commands.entity(entity).remove::<TechFlash>();
+        }
+    }
+    for (entity, mut flash) in wall_bounce_flashes.iter_mut() {
+        if flash.frames_remaining > 0 {
+            flash.frames_remaining -= 1;
+        } else {
+            commands.entity(entity).remove::<WallBounceFlash>();
+        }
+    }
+    for (entity, mut flash) in spike_flashes.iter_mut() {
+        if flash.frames_remaining > 0 {
+            flash.frames_remaining -= 1;
+        } else {
+            commands.entity(entity).remove::<SpikeFlash>();
+        }
+    }
+}
+
 // ==================== VISUAL FEEDBACK ====================
 
 /// Show stumble direction arrow at player's feet
 pub fn visualize_stumble_direction(
     mut gizmos: Gizmos,
-    query: Query<(&Transform, &StumbleState)>,
+    query: Query<(&Transform, &Facing, &StumbleState)>,
 ) {
-    for (transform, stumble) in query.iter() {
+    for (transform, facing, stumble) in query.iter() {
         let pos = transform.translation.truncate();
         let feet_pos = pos + Vec2::new(0.0, -100.0);  // Below character
 
@@ -337,8 +370,8 @@ pub fn visualize_stumble_direction(
 
         // Draw directional arrow
         let arrow_dir = match stumble.direction {
-            StumbleDirection::Backward => Vec2::new(-20.0, 0.0),
-            StumbleDirection::Forward => Vec2::new(20.0, 0.0),
+            StumbleDirection::Backward => Vec2::new(-20.0 * facing.sign(), 0.0),
+            StumbleDirection::Forward => Vec2::new(20.0 * facing.sign(), 0.0),
             StumbleDirection::Down => Vec2::new(0.0, -20.0),
         };
 
@@ -386,30 +419,23 @@ pub fn visualize_stumble_state(
 
 /// Dark red flash on successful tech
 pub fn tech_flash_effect(
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut TechFlash, &mut Sprite)>,
+    mut query: Query<(&TechFlash, &mut Sprite)>,
 ) {
-    for (entity, mut flash, mut sprite) in query.iter_mut() {
+    for (flash, mut sprite) in query.iter_mut() {
         if flash.frames_remaining > 0 {
             // Dark red flash (subtle)
             let intensity = flash.frames_remaining as f32 / 6.0;
             sprite.color = Color::srgb(0.6 * intensity, 0.1 * intensity, 0.1 * intensity);
-
-            flash.frames_remaining -= 1;
-        } else {
-            // Flash complete, remove component
-            commands.entity(entity).remove::<TechFlash>();
         }
     }
 }
 
 /// Visual effect for wall bounce (expanding impact circles)
 pub fn wall_bounce_visual(
-    mut commands: Commands,
     mut gizmos: Gizmos,
-    mut query: Query<(Entity, &Transform, &mut WallBounceFlash)>,
+    query: Query<(&Transform, &WallBounceFlash)>,
 ) {
-    for (entity, transform, mut flash) in query.iter_mut() {
+    for (transform, flash) in query.iter() {
         if flash.frames_remaining > 0 {
             let pos = transform.translation.truncate();
 
@@ -424,11 +450,6 @@ pub fn wall_bounce_visual(
 
                 gizmos.circle_2d(pos, radius, color);
             }
-
-            flash.frames_remaining -= 1;
-        } else {
-            // Effect complete
-            commands.entity(entity).remove::<WallBounceFlash>();
         }
     }
 }
@@ -437,11 +458,10 @@ pub fn wall_bounce_visual(
 ///
 /// Creates a powerful impact flash with expanding shockwave
 pub fn spike_finisher_visual(
-    mut commands: Commands,
     mut gizmos: Gizmos,
-    mut query: Query<(Entity, &Transform, &mut SpikeFlash, &mut Sprite)>,
+    mut query: Query<(&Transform, &SpikeFlash, &mut Sprite)>,
 ) {
-    for (entity, transform, mut flash, mut sprite) in query.iter_mut() {
+    for (transform, flash, mut sprite) in query.iter_mut() {
         if flash.frames_remaining > 0 {
             let pos = transform.translation.truncate();
             let progress = 1.0 - (flash.frames_remaining as f32 / 12.0);
@@ -486,11 +506,6 @@ pub fn spike_finisher_visual(
                 pos + Vec2::new(0.0, line_length),
                 line_color,
             );
-
-            flash.frames_remaining -= 1;
-        } else {
-            // Effect complete
-            commands.entity(entity).remove::<SpikeFlash>();
         }
     }
 }
@@ -0,0 +1,267 @@
+use std::fmt;
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use crate::systems::input::CurrentInputs;
+use crate::systems::simulation::{clear_simulation_events, run_simulation_tick};
+use crate::systems::snapshot::SimState;
+
+/// Frames re-simulated per check in `--sync-test` mode (2 seconds)
+pub const SYNC_TEST_WINDOW: usize = 120;
+
+/// First point where two runs of the same inputs disagree
+#[derive(Debug, Clone, PartialEq)]
+pub struct Desync {
+    /// Simulation frame of the first state that differs
+    pub frame: u32,
+    /// Path to the first differing field, e.g. `fighters[1].guard.current`
+    pub field: String,
+    /// Value in the first (reference) run
+    pub expected: String,
+    /// Value in the second run
+    pub actual: String,
+}
+
+impl fmt::Display for Desync {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "desync at frame {}: {} was {} but re-simulated as {}",
+            self.frame, self.field, self.expected, self.actual
+        )
+    }
+}
+
+/// Restore `start`, run one tick per entry of `inputs`, and return the state
+/// after every tick
+pub fn simulate_inputs(world: &mut World, start: &SimState, inputs: &[CurrentInputs]) -> Vec<SimState> {
+    start.restore(world);
+    inputs.iter().map(|frame_inputs| simulate_tick(world, frame_inputs)).collect()
+}
+
+/// Run one tick with `inputs` and return the state after it
+fn simulate_tick(world: &mut World, inputs: &CurrentInputs) -> SimState {
+    *world.resource_mut::<CurrentInputs>() = *inputs;
+    run_simulation_tick(world);
+    SimState::capture(world)
+}
+
+/// Compare two runs frame by frame, returning the first divergence
+pub fn compare_runs(expected: &[SimState], actual: &[SimState]) -> Option<Desync> {
+    expected
+        .iter()
+        .zip(actual)
+        .find(|(expected, actual)| expected.checksum() != actual.checksum())
+        .map(|(expected, actual)| diff_states(expected, actual))
+}
+
+/// Locate the first field that differs between two states
+///
+/// Diffs the pretty-printed RON line by line and rebuilds the field path from
+/// indentation, so new snapshot fields are covered without extra code.
+pub fn diff_states(expected: &SimState, actual: &SimState) -> Desync {
+    let pretty = |state: &SimState| {
+        ron::ser::to_string_pretty(state, PrettyConfig::default()).expect("SimState is always serializable")
+    };
+    let expected_text = pretty(expected);
+    let actual_text = pretty(actual);
+    let expected_lines: Vec<&str> = expected_text.lines().collect();
+    let actual_lines: Vec<&str> = actual_text.lines().collect();
+
+    let index = expected_lines
+        .iter()
+        .zip(&actual_lines)
+        .position(|(a, b)| a != b)
+        .unwrap_or(expected_lines.len().min(actual_lines.len()).saturating_sub(1));
+
+    let value = |lines: &[&str]| {
+        lines
+            .get(index)
+            .map(|line| field_value(line).to_string())
+            .unwrap_or_else(|| "<missing>".to_string())
+    };
+
+    Desync {
+        frame: expected.frame,
+        field: field_path(&expected_lines, index),
+        expected: value(&expected_lines),
+        actual: value(&actual_lines),
+    }
+}
+
+const INDENT: usize = 4;
+
+/// `name` if the line is a `name: value` struct field
+fn field_name(text: &str) -> Option<&str> {
+    let (name, _) = text.split_once(':')?;
+    (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')).then_some(name)
+}
+
+fn field_value(line: &str) -> &str {
+    let text = line.trim();
+    let value = match field_name(text) {
+        Some(name) => text[name.len() + 1..].trim(),
+        None => text,
+    };
+    value.trim_end_matches(',')
+}
+
+/// Rebuild the path of the field on `lines[index]` from indentation
+fn field_path(lines: &[&str], index: usize) -> String {
+    // segments[d] names the value at indentation depth d + 1
+    let mut segments: Vec<String> = Vec::new();
+    // Element counters for list items at each depth
+    let mut element_counts: Vec<usize> = Vec::new();
+
+    for line in lines.iter().take(index + 1) {
+        let text = line.trim_start();
+        let depth = (line.len() - text.len()) / INDENT;
+        if depth == 0 || text.starts_with(')') || text.starts_with(']') {
+            continue;
+        }
+
+        // Leaving a nested value drops its segments and restarts deeper lists
+        segments.truncate(depth - 1);
+        element_counts.resize(depth + 1, 0);
+
+        match field_name(text) {
+            Some(name) => segments.push(name.to_string()),
+            None => {
+                segments.push(format!("[{}]", element_counts[depth]));
+                element_counts[depth] += 1;
+            }
+        }
+    }
+
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| if i == 0 || segment.starts_with('[') { segment.clone() } else { format!(".{}", segment) })
+        .collect()
+}
+
+// ==================== LIVE SYNC TEST ====================
+
+/// `--sync-test`: continuously re-simulate recent frames during play
+///
+/// Records the inputs and resulting state of every tick. Every
+/// `SYNC_TEST_WINDOW` frames the window is re-simulated from its starting
+/// snapshot and compared with what was actually played, catching state that
+/// lives outside `SimState` (statics, `Local`s, stray resources) before it
+/// causes a rollback desync online.
+#[derive(Resource, Default)]
+pub struct SyncTest {
+    start: Option<SimState>,
+    inputs: Vec<CurrentInputs>,
+    states: Vec<SimState>,
+    windows_verified: u32,
+}
+
+/// Forget the previous match's window
+pub fn reset_sync_test(sync_test: Option<ResMut<SyncTest>>) {
+    if let Some(mut sync_test) = sync_test {
+        *sync_test = SyncTest::default();
+    }
+}
+
+/// Record the tick that just ran, verifying the window once it is full
+pub fn sync_test_tick(world: &mut World) {
+    let Some(mut test) = world.remove_resource::<SyncTest>() else {
+        return;
+    };
+
+    let state = SimState::capture(world);
+    match test.start.take() {
+        None => test.start = Some(state),
+        Some(start) => {
+            test.inputs.push(*world.resource::<CurrentInputs>());
+            test.states.push(state);
+
+            if test.inputs.len() < SYNC_TEST_WINDOW {
+                test.start = Some(start);
+            } else {
+                // The last tick goes on its own: its events stand in for the
+                // live tick's, which presentation hasn't read yet
+                let (last, earlier) = test.inputs.split_last().expect("window is full");
+                let mut replayed = simulate_inputs(world, &start, earlier);
+                clear_simulation_events(world);
+                replayed.push(simulate_tick(world, last));
+
+                match compare_runs(&test.states, &replayed) {
+                    Some(desync) => error!("Sync test: {}", desync),
+                    None => {
+                        test.windows_verified += 1;
+                        debug!("Sync test: {} windows deterministic", test.windows_verified);
+                    }
+                }
+
+                // Continue the match exactly as it was played
+                let live = test.states.pop().expect("window is full");
+                live.restore(world);
+                test.start = Some(live);
+                test.inputs.clear();
+                test.states.clear();
+            }
+        }
+    }
+
+    world.insert_resource(test);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::breath::{MatchState, RoundEndEvent, RoundEndReason};
+    use crate::systems::simulation::{SimFrame, SimulationTick};
+
+    fn state_with_timer(round_frames: u32) -> SimState {
+        SimState {
+            frame: 7,
+            match_state: MatchState { round_frames, ..default() },
+            winner: None,
+            fighters: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_field_path() {
+        let desync = diff_states(&state_with_timer(300), &state_with_timer(299));
+        assert_eq!(desync.frame, 7);
+        assert_eq!(desync.field, "match_state.round_frames");
+        assert_eq!(desync.expected, "300");
+        assert_eq!(desync.actual, "299");
+    }
+
+    #[test]
+    fn test_identical_runs_have_no_desync() {
+        let run = vec![state_with_timer(10), state_with_timer(9)];
+        assert!(compare_runs(&run, &run.clone()).is_none());
+        assert_eq!(run[0].checksum(), state_with_timer(10).checksum());
+        assert_ne!(run[0].checksum(), run[1].checksum());
+    }
+
+    #[test]
+    fn test_resimulation_does_not_repeat_events() {
+        let mut world = World::new();
+        world.init_resource::<SimFrame>();
+        world.init_resource::<CurrentInputs>();
+        world.init_resource::<SyncTest>();
+        world.init_resource::<Events<RoundEndEvent>>();
+        let mut schedule = Schedule::new(SimulationTick);
+        schedule.add_systems(|mut round_ends: EventWriter<RoundEndEvent>| {
+            round_ends.send(RoundEndEvent { winner: Entity::PLACEHOLDER, reason: RoundEndReason::Timeout });
+        });
+        world.add_schedule(schedule);
+
+        // Presentation reads after every tick and should see each tick's event once
+        let mut presentation = world.resource::<Events<RoundEndEvent>>().get_reader();
+        let ticks = SYNC_TEST_WINDOW * 2 + 1;
+        let mut seen = 0;
+        for _ in 0..ticks {
+            run_simulation_tick(&mut world);
+            sync_test_tick(&mut world);
+            seen += presentation.read(world.resource::<Events<RoundEndEvent>>()).count();
+        }
+        assert_eq!(seen, ticks);
+        assert_eq!(world.resource::<SyncTest>().windows_verified, 2);
+    }
+}
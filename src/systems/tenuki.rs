@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::components::character::Player;
+use crate::components::combat::AttackProperty;
+use crate::components::movelist::Movelist;
+use crate::components::state::{AttackPhase, AttackType, CharacterState};
+use crate::events::combat_events::HitEvent;
+
+/// Frames the Tenuki ring stays up on the absorbing fighter
+const FLASH_FRAMES: u8 = 12;
+
+/// Tenuki (手抜き) - a Heavy that has taken its one Light hit and kept going
+///
+/// Inserted on the absorbing fighter and removed once that Heavy leaves its
+/// startup/active frames, so each armored Heavy soaks at most one Light.
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+pub struct Tenuki {
+    pub flash_frames: u8,
+}
+
+/// Sent when a Heavy absorbs a Light and keeps its attack going
+#[derive(Event, Debug, Clone)]
+pub struct TenukiEvent {
+    /// Fighter whose Heavy absorbed the hit
+    pub absorber: Entity,
+    /// Fighter whose Light was ignored
+    pub attacker: Entity,
+}
+
+/// Whether a fighter in `state` absorbs an incoming `incoming` attack
+///
+/// Only a Light is absorbed, only during the startup of a move with
+/// `LightArmor`, and only once per attack (`spent`). Heavies and Grabs
+/// still interrupt.
+pub fn can_absorb(state: &CharacterState, movelist: &Movelist, spent: bool, incoming: AttackType) -> bool {
+    if spent || incoming != AttackType::Light {
+        return false;
+    }
+    let CharacterState::Attacking { attack_type, direction, phase: AttackPhase::Startup } = state else {
+        return false;
+    };
+    movelist
+        .get_move(*attack_type, *direction)
+        .is_some_and(|move_data| move_data.properties.contains(&AttackProperty::LightArmor))
+}
+
+/// Mark the absorber and announce the read
+///
+/// The absorbed hit still deals its damage (and can drop the health state);
+/// `damage::apply_hit_reactions` and launchers skip it, so the Heavy keeps
+/// progressing.
+pub fn absorb_light_hits(
+    mut commands: Commands,
+    mut hit_events: EventReader<HitEvent>,
+    mut tenuki_events: EventWriter<TenukiEvent>,
+) {
+    for event in hit_events.read() {
+        if !event.absorbed {
+            continue;
+        }
+
+        commands.entity(event.defender).insert(Tenuki { flash_frames: FLASH_FRAMES });
+        tenuki_events.send(TenukiEvent {
+            absorber: event.defender,
+            attacker: event.attacker,
+        });
+
+        info!("TENUKI! {:?} absorbed a Light and keeps attacking", event.defender);
+    }
+}
+
+/// Count the ring down; drop the marker once the Heavy is past its active frames
+pub fn tick_tenuki(
+    mut commands: Commands,
+    mut query: Query<(Entity, &CharacterState, &mut Tenuki)>,
+) {
+    for (entity, state, mut tenuki) in query.iter_mut() {
+        tenuki.flash_frames = tenuki.flash_frames.saturating_sub(1);
+
+        let still_attacking = matches!(
+            state,
+            CharacterState::Attacking { phase: AttackPhase::Startup | AttackPhase::Active, .. }
+        );
+        if !still_attacking {
+            commands.entity(entity).remove::<Tenuki>();
+        }
+    }
+}
+
+/// Ink-dark ring around a fighter that just absorbed a hit
+pub fn tenuki_visual(
+    mut gizmos: Gizmos,
+    query: Query<(&Transform, &Tenuki)>,
+) {
+    for (transform, tenuki) in query.iter() {
+        if tenuki.flash_frames == 0 {
+            continue;
+        }
+
+        let pos = transform.translation.truncate();
+        let fade = tenuki.flash_frames as f32 / FLASH_FRAMES as f32;
+        let radius = 70.0 + (1.0 - fade) * 20.0;
+
+        gizmos.circle_2d(pos, radius, Color::srgba(0.35, 0.2, 0.6, fade));
+        gizmos.circle_2d(pos, radius - 6.0, Color::srgba(0.9, 0.9, 1.0, fade * 0.6));
+    }
+}
+
+/// Debug: Log Tenuki reads with the players involved
+pub fn debug_tenuki_events(
+    mut tenuki_events: EventReader<TenukiEvent>,
+    players: Query<&Player>,
+) {
+    for event in tenuki_events.read() {
+        debug!(
+            "TenukiEvent: absorber={:?}, attacker={:?}",
+            players.get(event.absorber).ok(),
+            players.get(event.attacker).ok()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::movelist::AttackDirection;
+    use crate::data::character::CharacterData;
+
+    const DEFAULT_CHARACTER_FILE: &str = include_str!("../../assets/data/characters/default.character.ron");
+
+    fn heavy(phase: AttackPhase) -> CharacterState {
+        CharacterState::Attacking {
+            attack_type: AttackType::Heavy,
+            direction: AttackDirection::Neutral,
+            phase,
+        }
+    }
+
+    #[test]
+    fn test_heavy_startup_absorbs_one_light() {
+        let movelist = CharacterData::parse(DEFAULT_CHARACTER_FILE).unwrap().movelist();
+
+        assert!(can_absorb(&heavy(AttackPhase::Startup), &movelist, false, AttackType::Light));
+        // Only once, only Lights, only in startup
+        assert!(!can_absorb(&heavy(AttackPhase::Startup), &movelist, true, AttackType::Light));
+        assert!(!can_absorb(&heavy(AttackPhase::Startup), &movelist, false, AttackType::Heavy));
+        assert!(!can_absorb(&heavy(AttackPhase::Startup), &movelist, false, AttackType::Grab));
+        assert!(!can_absorb(&heavy(AttackPhase::Active), &movelist, false, AttackType::Light));
+        assert!(!can_absorb(&CharacterState::Idle, &movelist, false, AttackType::Light));
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
-use crate::components::breath::{Breath, MatchState};
+use crate::components::breath::{Breath, MatchState, COUNTDOWN_FRAMES};
 use crate::components::character::Player;
-use crate::components::health::Health;
+use crate::components::health::{FinalStand, Health};
 
 /// Render breath indicators (circles) for each player
 pub fn render_breath_indicators(
@@ -94,15 +94,37 @@ pub fn render_health_bars(
     }
 }
 
+/// Mark a player holding Final Stand with a gold diamond by their health bar
+pub fn render_final_stand_indicators(
+    mut gizmos: Gizmos,
+    query: Query<&Player, With<FinalStand>>,
+) {
+    for player in query.iter() {
+        // Just past the end of the health bar
+        let center = match player {
+            Player::One => Vec2::new(-380.0, 260.0),
+            Player::Two => Vec2::new(620.0, 260.0),
+        };
+        let gold = Color::srgb(1.0, 0.85, 0.2);
+        let size = 10.0;
+
+        let top = center + Vec2::new(0.0, size);
+        let right = center + Vec2::new(size, 0.0);
+        let bottom = center - Vec2::new(0.0, size);
+        let left = center - Vec2::new(size, 0.0);
+        gizmos.linestrip_2d([top, right, bottom, left, top], gold);
+    }
+}
+
 /// Render round timer at top center
 pub fn render_round_timer(
     mut gizmos: Gizmos,
     match_state: Option<Res<MatchState>>,
 ) {
     if let Some(state) = match_state {
-        if !state.round_active && state.countdown > 0.0 {
+        if !state.round_active && state.countdown_frames > 0 {
             // Show countdown before round starts
-            let countdown_num = state.countdown.ceil() as u32;
+            let countdown_num = state.countdown_seconds().ceil() as u32;
 
             // Draw countdown number indicator (simple visualization)
             for i in 0..countdown_num {
@@ -115,7 +137,7 @@ pub fn render_round_timer(
             }
         } else if state.round_active {
             // Show round timer during active round
-            let time_remaining = state.round_time.max(0.0).ceil() as u32;
+            let time_remaining = state.round_seconds().ceil() as u32;
 
             // Timer bar at top center
             let bar_width = 200.0;
@@ -131,7 +153,7 @@ pub fn render_round_timer(
             );
 
             // Filled portion (time remaining)
-            let time_percent = state.round_time / state.max_round_time;
+            let time_percent = state.round_frames as f32 / state.max_round_frames as f32;
             let filled_width = bar_width * time_percent;
 
             // Color changes based on time remaining (green -> yellow -> red)
@@ -192,15 +214,15 @@ pub fn render_round_text_indicator(
 ) {
     if let Some(state) = match_state {
         // Draw visual indicator for round state
-        if !state.round_active && state.countdown > 0.0 {
+        if !state.round_active && state.countdown_frames > 0 {
             // Countdown phase - draw expanding ring
-            let size = 100.0 * (1.0 - (state.countdown / 3.0));
+            let size = 100.0 * (1.0 - (state.countdown_frames as f32 / COUNTDOWN_FRAMES as f32));
             gizmos.circle_2d(
                 Vec2::new(0.0, 0.0),
                 size,
                 Color::srgb(1.0, 1.0, 0.0),
             );
-        } else if !state.round_active && state.countdown <= 0.0 && !state.match_over {
+        } else if !state.round_active && state.countdown_frames == 0 && !state.match_over {
             // "FIGHT!" moment - draw flash effect
             gizmos.circle_2d(
                 Vec2::new(0.0, 0.0),